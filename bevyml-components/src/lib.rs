@@ -1,59 +1,68 @@
-use bevy_ecs::component::Component;
+use bevy_ecs::{
+    component::{Component, ComponentId, Components},
+    system::EntityCommands,
+};
 use bevy_reflect::Reflect;
+use bevyml_parser::inode::NodeType;
 use serde::{Deserialize, Serialize};
 
-macro_rules! element {
-    ($name:ident) => {
-        #[derive(Reflect, Component, Clone, Copy, Debug, Default, Serialize, Deserialize)]
-        pub struct $name;
+/// Resolves the marker component belonging to an element type, so generic spawn code can attach
+/// it without matching on every [`NodeType`] itself. Text and custom elements have no marker.
+pub trait NodeTypeMarker {
+    /// The marker's id, or `None` when it has not been registered in `components` yet.
+    fn marker_component_id(&self, components: &Components) -> Option<ComponentId>;
+
+    fn insert_marker(&self, entity: &mut EntityCommands<'_>);
+}
+
+/// Declares a `<Variant>Element` marker for every variant listed by
+/// [`for_each_element_type!`](bevyml_parser::for_each_element_type), so new elements get their
+/// marker without touching this crate.
+macro_rules! elements {
+    ($($variant:ident),* $(,)?) => {
+        paste::paste! {
+            $(
+                #[derive(Reflect, Component, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+                pub struct [<$variant Element>];
+            )*
+
+            impl NodeTypeMarker for NodeType {
+                fn marker_component_id(&self, components: &Components) -> Option<ComponentId> {
+                    match self {
+                        $(NodeType::$variant => components.component_id::<[<$variant Element>]>(),)*
+                        NodeType::Text | NodeType::Custom(_) => None,
+                    }
+                }
+
+                fn insert_marker(&self, entity: &mut EntityCommands<'_>) {
+                    match self {
+                        $(NodeType::$variant => {
+                            entity.insert([<$variant Element>]);
+                        })*
+                        NodeType::Text | NodeType::Custom(_) => {}
+                    }
+                }
+            }
+        }
     };
 }
 
-element!(HtmlElement);
-element!(HeadElement);
-element!(BodyElement);
-element!(TitleElement);
-element!(MetaElement);
-element!(LinkElement);
-element!(StyleElement);
-element!(ScriptElement);
-element!(DivElement);
-element!(SpanElement);
-element!(PElement);
-element!(AElement);
-element!(ImgElement);
-element!(ButtonElement);
-element!(InputElement);
-element!(LabelElement);
-element!(TextareaElement);
-element!(SelectElement);
-element!(OptionElement);
-element!(UlElement);
-element!(OlElement);
-element!(LiElement);
-element!(TableElement);
-element!(TheadElement);
-element!(TbodyElement);
-element!(TfootElement);
-element!(TrElement);
-element!(ThElement);
-element!(TdElement);
-element!(HeaderElement);
-element!(FooterElement);
-element!(NavElement);
-element!(MainElement);
-element!(SectionElement);
-element!(ArticleElement);
-element!(AsideElement);
-element!(FormElement);
-element!(CanvasElement);
-element!(SvgElement);
-element!(BrElement);
-element!(HrElement);
-
-element!(H1Element);
-element!(H2Element);
-element!(H3Element);
-element!(H4Element);
-element!(H5Element);
-element!(H6Element);
+bevyml_parser::for_each_element_type!(elements);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::world::World;
+
+    #[test]
+    fn div_resolves_to_the_div_element_marker() {
+        let mut world = World::new();
+        assert_eq!(NodeType::Div.marker_component_id(world.components()), None);
+        let div = world.register_component::<DivElement>();
+        assert_eq!(
+            NodeType::Div.marker_component_id(world.components()),
+            Some(div)
+        );
+        assert_eq!(NodeType::Text.marker_component_id(world.components()), None);
+    }
+}
@@ -1,9 +1,8 @@
 use bevy::{
-    ecs::relationship::RelatedSpawnerCommands,
     log::{DEFAULT_FILTER, Level, LogPlugin},
     prelude::*,
 };
-use bevyml::{BevyNodeTree, BevymlAsset, BevymlAssetPlugin};
+use bevyml::{BevymlAsset, BevymlAssetPlugin, spawn_bevy_tree};
 
 fn main() {
     App::new()
@@ -46,7 +45,7 @@ fn spawn_ui(
             let roots = &ml.roots;
 
             for root in roots {
-                spawn_tree(&mut commands, root);
+                spawn_bevy_tree(&mut commands, root);
             }
 
             *spawned = true;
@@ -54,27 +53,3 @@ fn spawn_ui(
         None => bevy::log::error!("Failed to load UI root."),
     }
 }
-
-fn spawn_tree(commands: &mut Commands, tree: &BevyNodeTree) {
-    let mut entity = commands.spawn(tree.node.clone());
-    if let Some(text) = tree.text.clone() {
-        entity.insert(text);
-    }
-    entity.with_children(|parent| {
-        for child in &tree.children {
-            spawn_tree_child(parent, child);
-        }
-    });
-}
-
-fn spawn_tree_child(parent: &mut RelatedSpawnerCommands<'_, ChildOf>, tree: &BevyNodeTree) {
-    let mut entity = parent.spawn(tree.node.clone());
-    if let Some(text) = tree.text.clone() {
-        entity.insert(text);
-    }
-    entity.with_children(|parent| {
-        for child in &tree.children {
-            spawn_tree_child(parent, child);
-        }
-    });
-}
@@ -1,4 +1,4 @@
-use bevyml_parser::{BevymlParser, tree_sitter::LanguageError};
+use bevyml_parser::{BevymlParser, GrammarError};
 use clap::{Args, Parser, Subcommand};
 use std::{
     fmt, io,
@@ -31,6 +31,9 @@ struct ParseArgs {
     /// File system path to the Bevyml file you want to inspect.
     #[arg(value_name = "PATH", default_value = ".")]
     path: PathBuf,
+    /// Also print each node's attributes and unsupported inline styles.
+    #[arg(short, long)]
+    verbose: bool,
 }
 
 #[derive(Debug)]
@@ -40,7 +43,7 @@ enum CliError {
         action: &'static str,
         source: io::Error,
     },
-    Language(LanguageError),
+    Language(GrammarError),
     NotAFile(PathBuf),
 }
 
@@ -80,8 +83,8 @@ impl std::error::Error for CliError {
     }
 }
 
-impl From<LanguageError> for CliError {
-    fn from(err: LanguageError) -> Self {
+impl From<GrammarError> for CliError {
+    fn from(err: GrammarError) -> Self {
         Self::Language(err)
     }
 }
@@ -106,7 +109,11 @@ async fn run_parse(args: ParseArgs) -> anyhow::Result<()> {
     let mut parser = BevymlParser::try_new()?;
     let parse_start = Instant::now();
     let tree = parser.parse(&content)?;
-    tree.pretty_print();
+    if args.verbose {
+        tree.pretty_print_verbose();
+    } else {
+        tree.pretty_print();
+    }
     let parse_duration = parse_start.elapsed();
 
     println!("Parsing took {:.3}us", parse_duration.as_micros());
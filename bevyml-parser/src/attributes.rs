@@ -2,21 +2,26 @@ use bevy_color::{palettes::basic, Color, Srgba};
 use bevy_ecs::component::Component;
 use bevy_log::warn;
 use bevy_reflect::Reflect;
-use bevy_ui::{AlignItems, BorderRadius, Display, JustifyContent, UiRect, Val};
+use bevy_ui::{
+    AlignContent, AlignItems, AlignSelf, BorderRadius, Display, FlexDirection, FlexWrap,
+    JustifyContent, Overflow, OverflowAxis, PositionType, ShadowStyle, UiRect, Val,
+};
 use smallvec::SmallVec;
-use std::{borrow::Cow, mem::Discriminant};
 
-#[derive(Clone, Debug, PartialEq, Eq, Reflect)]
-pub struct ClassList<Str = String> {
-    pub raw: Str,
-    pub classes: SmallVec<[Str; 4]>,
-}
+use crate::entities::escape_attribute;
+use crate::inode::BASE_FONT_PX;
+use crate::named_colors::extended_color;
+pub use crate::raw::ClassList;
+use crate::raw::{split_declarations, DeclarationError};
+use std::{borrow::Cow, collections::HashMap, mem::Discriminant};
 
 #[derive(Clone, Debug, PartialEq, Reflect)]
 pub struct StyleAttribute<Str = String> {
     pub raw: Str,
     pub declarations: SmallVec<[StyleDeclaration; 8]>,
     pub unsupported: SmallVec<[UnsupportedStyle<Str>; 4]>,
+    /// Indices into `declarations` of the ones marked `!important`.
+    pub important: SmallVec<[usize; 2]>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Reflect)]
@@ -25,6 +30,257 @@ pub struct UnsupportedStyle<Str = String> {
     pub value: Str,
 }
 
+/// A problem found while parsing a `style` value; the declaration it refers to is skipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleWarning {
+    pub property: String,
+    pub value: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for StyleWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// What the declarations of a `style` value are parsed against: the size in pixels `rem` and `em`
+/// lengths resolve to, and the warnings collected along the way.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StyleContext {
+    pub root_font_px: f32,
+    pub warnings: Vec<StyleWarning>,
+}
+
+impl StyleContext {
+    pub fn new(root_font_px: f32) -> Self {
+        Self {
+            root_font_px,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl Default for StyleContext {
+    fn default() -> Self {
+        Self::new(BASE_FONT_PX)
+    }
+}
+
+/// State of the `hidden` attribute. `until-found` hides the content visually while keeping it
+/// searchable, so it is not collapsed out of layout like plain `hidden`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum Hidden {
+    #[default]
+    No,
+    Yes,
+    UntilFound,
+}
+
+/// Enumerated `draggable` state; anything other than `true`/`false` falls back to `auto`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum Draggable {
+    True,
+    False,
+    #[default]
+    Auto,
+}
+
+impl Draggable {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Draggable::True => "true",
+            Draggable::False => "false",
+            Draggable::Auto => "auto",
+        }
+    }
+}
+
+/// Enumerated `spellcheck` state; a bare attribute means `true` and an unknown value leaves the
+/// decision to the element's default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum SpellCheck {
+    True,
+    False,
+    #[default]
+    Default,
+}
+
+impl SpellCheck {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SpellCheck::True => "true",
+            SpellCheck::False => "false",
+            SpellCheck::Default => "default",
+        }
+    }
+}
+
+/// Pointer shape requested through the `cursor` style; a system can apply it to the window while
+/// the element is hovered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub enum CursorStyle {
+    #[default]
+    Auto,
+    Default,
+    Pointer,
+    Text,
+    Move,
+    Grab,
+    Grabbing,
+    Wait,
+    Progress,
+    Help,
+    Crosshair,
+    NotAllowed,
+    ColResize,
+    RowResize,
+}
+
+/// Every `data-*` attribute of an element keyed by the name after `data-`, so systems can look
+/// values up directly instead of scanning [`Attributes`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub struct DataSet(pub HashMap<String, Option<String>>);
+
+impl DataSet {
+    /// Collects the `data-*` entries of `attributes`, or `None` when there are none.
+    pub fn from_attributes<Str: AsRef<str>>(attributes: &Attributes<Str>) -> Option<Self> {
+        let entries: HashMap<_, _> = attributes
+            .items
+            .iter()
+            .filter_map(|attribute| match attribute {
+                Attribute::Data { key, value } => Some((
+                    key.as_ref().to_string(),
+                    value.as_ref().map(|value| value.as_ref().to_string()),
+                )),
+                _ => None,
+            })
+            .collect();
+        (!entries.is_empty()).then_some(DataSet(entries))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.as_deref())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+}
+
+/// Inline event handlers of an element, keyed by event name without the `on` prefix, so
+/// `onclick="save"` becomes `click -> save`. Apps dispatch to handlers they registered under those
+/// names; nothing is executed. Only present when [`ParseSettings::event_bindings`] is on.
+///
+/// [`ParseSettings::event_bindings`]: crate::settings::ParseSettings::event_bindings
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub struct EventBindings(pub HashMap<String, String>);
+
+impl EventBindings {
+    /// Collects the `on*` entries of `attributes`, or `None` when there are none.
+    pub fn from_attributes<Str: AsRef<str>>(attributes: &Attributes<Str>) -> Option<Self> {
+        let entries: HashMap<_, _> = attributes
+            .items
+            .iter()
+            .filter_map(|attribute| match attribute {
+                Attribute::Custom { name, value } => {
+                    let event = event_name(name.as_ref())?;
+                    let handler = value.as_ref()?.as_ref().trim();
+                    (!handler.is_empty()).then(|| (event.to_ascii_lowercase(), handler.to_string()))
+                }
+                _ => None,
+            })
+            .collect();
+        (!entries.is_empty()).then_some(EventBindings(entries))
+    }
+
+    pub fn get(&self, event: &str) -> Option<&str> {
+        self.0.get(event).map(String::as_str)
+    }
+}
+
+/// The event an inline handler attribute like `onclick` listens for.
+pub fn event_name(attribute_name: &str) -> Option<&str> {
+    let prefix = attribute_name.get(..2)?;
+    let event = &attribute_name[2..];
+    (prefix.eq_ignore_ascii_case("on") && !event.is_empty()).then_some(event)
+}
+
+/// Families requested through `font-family`, most preferred first. Bevy needs a font handle, so
+/// mapping names to fonts is left to the app; generic families like `monospace` are kept as
+/// written.
+#[derive(Clone, Debug, PartialEq, Eq, Component, Reflect)]
+pub struct FontFamily(pub SmallVec<[String; 2]>);
+
+/// What a `transition` declaration asks to animate. Bevy UI does not tween on its own, so this is
+/// only the intent, for an app system to act on when the property changes.
+#[derive(Clone, Debug, PartialEq, Component, Reflect)]
+pub struct TransitionSpec {
+    /// The transitioned property, lowercased; `all` when the declaration names none.
+    pub property: String,
+    pub duration_secs: f32,
+    pub delay_secs: f32,
+    pub easing: TransitionEasing,
+}
+
+/// The keyword timing functions of `transition`; `cubic-bezier()` and `steps()` are unsupported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum TransitionEasing {
+    Linear,
+    #[default]
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    StepStart,
+    StepEnd,
+}
+
+/// Case change `text-transform` applies to the text an element spawns; the markup keeps the text
+/// as written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum TextTransform {
+    #[default]
+    None,
+    Uppercase,
+    Lowercase,
+    Capitalize,
+}
+
+impl TextTransform {
+    pub fn apply(self, text: &str) -> String {
+        self.apply_after(text, None)
+    }
+
+    /// Like [`TextTransform::apply`] for text continuing after `previous`, so `capitalize` leaves
+    /// a word split across inline elements, like `un<b>break</b>able`, with a single capital.
+    pub(crate) fn apply_after(self, text: &str, previous: Option<char>) -> String {
+        match self {
+            TextTransform::None => text.to_string(),
+            TextTransform::Uppercase => text.to_uppercase(),
+            TextTransform::Lowercase => text.to_lowercase(),
+            TextTransform::Capitalize => {
+                let mut capitalized = String::with_capacity(text.len());
+                let mut word_start = previous.is_none_or(char::is_whitespace);
+                for ch in text.chars() {
+                    if word_start && ch.is_alphanumeric() {
+                        capitalized.extend(ch.to_uppercase());
+                        word_start = false;
+                    } else {
+                        capitalized.push(ch);
+                        if ch.is_whitespace() {
+                            word_start = true;
+                        } else if ch.is_alphanumeric() {
+                            word_start = false;
+                        }
+                    }
+                }
+                capitalized
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Reflect)]
 pub struct BorderStyle {
     pub thickness: UiRect,
@@ -60,14 +316,35 @@ pub enum StyleDeclaration {
     BorderBottom(Val),
     BorderRadius(BorderRadius),
     BackgroundColor(Color),
+    TextColor(Color),
     AlignItems(AlignItems),
     JustifyContent(JustifyContent),
     RowGap(Val),
     ColumnGap(Val),
     Gap { row: Val, column: Val },
     FlexBasis(Val),
+    FlexGrow(f32),
+    FlexShrink(f32),
+    Opacity(f32),
+    LineClamp(u32),
+    Cursor(CursorStyle),
+    BoxShadow(Vec<ShadowStyle>),
+    FontSize(Val),
+    FontFamily(FontFamily),
+    FlexDirection(FlexDirection),
+    FlexWrap(FlexWrap),
+    Position(PositionType),
+    AlignSelf(AlignSelf),
+    AlignContent(AlignContent),
+    Transition(TransitionSpec),
+    TextTransform(TextTransform),
+    Overflow(Overflow),
+    OverflowX(OverflowAxis),
+    OverflowY(OverflowAxis),
+    AspectRatio(f32),
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, PartialEq, Reflect)]
 pub enum Attribute<Str = String> {
     Id(Str),
@@ -76,13 +353,13 @@ pub enum Attribute<Str = String> {
     Title(Str),
     Lang(Str),
     Dir(Str),
-    Hidden(bool),
+    Hidden(Hidden),
     TabIndex(Str),
     Role(Str),
     AccessKey(Str),
-    Draggable(bool),
+    Draggable(Draggable),
     ContentEditable(bool),
-    SpellCheck(bool),
+    SpellCheck(SpellCheck),
     InputMode(Str),
     EnterKeyHint(Str),
     Translate(bool),
@@ -95,6 +372,8 @@ pub enum Attribute<Str = String> {
     Required(bool),
     Multiple(bool),
     Autofocus(bool),
+    Inert(bool),
+    Open(bool),
     Href(Str),
     Src(Str),
     Alt(Str),
@@ -143,6 +422,9 @@ pub enum Attribute<Str = String> {
     PlaysInline(bool),
     Poster(Str),
     Preload(Str),
+    Start(Str),
+    ColSpan(Str),
+    RowSpan(Str),
     Aria { name: Str, value: Option<Str> },
     Custom { name: Str, value: Option<Str> },
 }
@@ -156,7 +438,28 @@ pub struct Attributes<Str = String> {
 
 impl<'a> Attributes<Cow<'a, str>> {
     pub fn add_raw_attribute(&mut self, name: Cow<'a, str>, value: Option<Cow<'a, str>>) {
-        let attribute = build_attribute(name, value);
+        self.add_raw_attribute_with(name, value, AttributeOptions::default());
+    }
+
+    /// Like [`Attributes::add_raw_attribute`], but boolean attributes such as `disabled` only
+    /// count as set in their HTML forms: bare, empty, or repeating the attribute's name. Any other
+    /// value reads as unset with a warning, catching typos like `disabled="tru"`.
+    pub fn add_raw_attribute_strict(&mut self, name: Cow<'a, str>, value: Option<Cow<'a, str>>) {
+        let options = AttributeOptions {
+            strict_bool: true,
+            ..AttributeOptions::default()
+        };
+        self.add_raw_attribute_with(name, value, options);
+    }
+
+    /// Adds an attribute parsed under `options`.
+    pub fn add_raw_attribute_with(
+        &mut self,
+        name: Cow<'a, str>,
+        value: Option<Cow<'a, str>>,
+        options: AttributeOptions,
+    ) {
+        let attribute = build_attribute(name, value, options);
         self.push_attribute(attribute);
     }
 
@@ -171,11 +474,39 @@ impl<'a> Attributes<Cow<'a, str>> {
 
 impl Attributes<String> {
     pub fn add_raw_attribute(&mut self, name: &str, value: Option<String>) {
-        let attribute = build_attribute(Cow::Owned(name.to_string()), value.map(Cow::Owned));
+        let attribute = build_attribute(
+            Cow::Owned(name.to_string()),
+            value.map(Cow::Owned),
+            AttributeOptions::default(),
+        );
         self.push_attribute(attribute.into_owned());
     }
 }
 
+impl<Str: AsRef<str>> Attributes<Str> {
+    /// The element's `id`, if it has one.
+    pub fn id(&self) -> Option<&str> {
+        self.items.iter().find_map(|attribute| match attribute {
+            Attribute::Id(id) => Some(id.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// The element's parsed `class` attribute, if it has one.
+    pub fn class_list(&self) -> Option<&ClassList<Str>> {
+        self.items.iter().find_map(|attribute| match attribute {
+            Attribute::Class(classes) => Some(classes),
+            _ => None,
+        })
+    }
+
+    /// Whether `class` appears in the element's class list.
+    pub fn has_class(&self, class: &str) -> bool {
+        self.class_list()
+            .is_some_and(|classes| classes.contains(class))
+    }
+}
+
 impl<Str> Attributes<Str> {
     fn push_attribute(&mut self, attribute: Attribute<Str>) {
         if attribute.is_multi() {
@@ -196,36 +527,108 @@ impl<Str> Attributes<Str> {
         self.items.push(attribute);
         self.index.push((discriminant, index));
     }
+
+    /// Scales the pixel lengths of every inline `style` declaration, see [`StyleDeclaration::scale_px`].
+    pub fn scale_px(&mut self, factor: f32) {
+        for attribute in &mut self.items {
+            if let Attribute::Style(style) = attribute {
+                style.scale_px(factor);
+            }
+        }
+    }
+
+    /// Clamps the pixel lengths of every inline `style` declaration, returning how many changed.
+    pub fn clamp_px(&mut self, max: f32) -> usize {
+        self.items
+            .iter_mut()
+            .map(|attribute| match attribute {
+                Attribute::Style(style) => style.clamp_px(max),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Removes the first attribute of the given kind and shifts the index entries that pointed
+    /// past it. For multi-valued kinds (`data-*`, `aria-*`, custom) only the first match goes.
+    pub fn remove(&mut self, discriminant: Discriminant<Attribute<Str>>) -> Option<Attribute<Str>> {
+        let position = self
+            .items
+            .iter()
+            .position(|item| std::mem::discriminant(item) == discriminant)?;
+        let removed = self.items.remove(position);
+        self.index.retain(|(_, index)| *index != position);
+        for (_, index) in self.index.iter_mut() {
+            if *index > position {
+                *index -= 1;
+            }
+        }
+        Some(removed)
+    }
+}
+
+/// How raw attribute values are read, see [`Attributes::add_raw_attribute_with`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AttributeOptions {
+    /// Reads boolean attributes as [`Attributes::add_raw_attribute_strict`] does.
+    pub strict_bool: bool,
+    /// Size in pixels that `rem` and `em` lengths in inline styles resolve against.
+    pub root_font_px: f32,
+}
+
+impl Default for AttributeOptions {
+    fn default() -> Self {
+        Self {
+            strict_bool: false,
+            root_font_px: BASE_FONT_PX,
+        }
+    }
 }
 
-fn build_attribute<'a>(name: Cow<'a, str>, value: Option<Cow<'a, str>>) -> Attribute<Cow<'a, str>> {
+fn build_attribute<'a>(
+    name: Cow<'a, str>,
+    value: Option<Cow<'a, str>>,
+    options: AttributeOptions,
+) -> Attribute<Cow<'a, str>> {
     let normalized = name.as_ref().to_ascii_lowercase();
     let bool_value = parse_bool_attribute(value.as_deref());
+    // Presence-style boolean attributes; enumerated ones like `spellcheck` stay lenient.
+    let flag_value = || {
+        if options.strict_bool {
+            parse_strict_bool_attribute(&normalized, value.as_deref())
+        } else {
+            bool_value
+        }
+    };
     match normalized.as_str() {
         "id" => Attribute::Id(value.unwrap_or_else(empty_cow)),
         "class" => Attribute::Class(ClassList::parse(value.unwrap_or_else(empty_cow))),
-        "style" => Attribute::Style(StyleAttribute::parse(value.unwrap_or_else(empty_cow))),
+        "style" => Attribute::Style(StyleAttribute::parse_with_root_font_size(
+            value.unwrap_or_else(empty_cow),
+            options.root_font_px,
+        )),
         "title" => Attribute::Title(value.unwrap_or_else(empty_cow)),
         "lang" => Attribute::Lang(value.unwrap_or_else(empty_cow)),
         "dir" => Attribute::Dir(value.unwrap_or_else(empty_cow)),
-        "hidden" => Attribute::Hidden(bool_value),
+        "hidden" => Attribute::Hidden(parse_hidden_attribute(value.as_deref(), flag_value)),
         "tabindex" => Attribute::TabIndex(value.unwrap_or_else(empty_cow)),
         "role" => Attribute::Role(value.unwrap_or_else(empty_cow)),
         "accesskey" => Attribute::AccessKey(value.unwrap_or_else(empty_cow)),
-        "draggable" => Attribute::Draggable(bool_value),
+        "draggable" => Attribute::Draggable(parse_draggable_attribute(value.as_deref())),
         "contenteditable" => Attribute::ContentEditable(bool_value),
-        "spellcheck" => Attribute::SpellCheck(bool_value),
+        "spellcheck" => Attribute::SpellCheck(parse_spellcheck_attribute(value.as_deref())),
         "inputmode" => Attribute::InputMode(value.unwrap_or_else(empty_cow)),
         "enterkeyhint" => Attribute::EnterKeyHint(value.unwrap_or_else(empty_cow)),
         "translate" => Attribute::Translate(bool_value),
-        "enabled" => Attribute::Enabled(bool_value),
-        "disabled" => Attribute::Disabled(bool_value),
-        "checked" => Attribute::Checked(bool_value),
-        "selected" => Attribute::Selected(bool_value),
-        "readonly" => Attribute::ReadOnly(bool_value),
-        "required" => Attribute::Required(bool_value),
-        "multiple" => Attribute::Multiple(bool_value),
-        "autofocus" => Attribute::Autofocus(bool_value),
+        "enabled" => Attribute::Enabled(flag_value()),
+        "disabled" => Attribute::Disabled(flag_value()),
+        "checked" => Attribute::Checked(flag_value()),
+        "selected" => Attribute::Selected(flag_value()),
+        "readonly" => Attribute::ReadOnly(flag_value()),
+        "required" => Attribute::Required(flag_value()),
+        "multiple" => Attribute::Multiple(flag_value()),
+        "autofocus" => Attribute::Autofocus(flag_value()),
+        "inert" => Attribute::Inert(flag_value()),
+        "open" => Attribute::Open(flag_value()),
         "href" => Attribute::Href(value.unwrap_or_else(empty_cow)),
         "src" => Attribute::Src(value.unwrap_or_else(empty_cow)),
         "alt" => Attribute::Alt(value.unwrap_or_else(empty_cow)),
@@ -274,6 +677,9 @@ fn build_attribute<'a>(name: Cow<'a, str>, value: Option<Cow<'a, str>>) -> Attri
         "playsinline" => Attribute::PlaysInline(bool_value),
         "poster" => Attribute::Poster(value.unwrap_or_else(empty_cow)),
         "preload" => Attribute::Preload(value.unwrap_or_else(empty_cow)),
+        "start" => Attribute::Start(value.unwrap_or_else(empty_cow)),
+        "colspan" => Attribute::ColSpan(value.unwrap_or_else(empty_cow)),
+        "rowspan" => Attribute::RowSpan(value.unwrap_or_else(empty_cow)),
         _ if normalized.starts_with("data-") => {
             let key = match &name {
                 Cow::Borrowed(raw) => Cow::Borrowed(raw.get(5..).unwrap_or("")),
@@ -310,6 +716,49 @@ fn parse_bool_attribute(value: Option<&str>) -> bool {
     }
 }
 
+fn parse_hidden_attribute(value: Option<&str>, present: impl FnOnce() -> bool) -> Hidden {
+    match value {
+        Some(raw) if raw.trim().eq_ignore_ascii_case("until-found") => Hidden::UntilFound,
+        _ if present() => Hidden::Yes,
+        _ => Hidden::No,
+    }
+}
+
+fn parse_strict_bool_attribute(name: &str, value: Option<&str>) -> bool {
+    match value.map(str::trim) {
+        None | Some("") => true,
+        Some(raw) if raw.eq_ignore_ascii_case(name) => true,
+        Some(raw) => {
+            warn!(
+                "boolean attribute '{}' has non-standard value {:?}, treating it as unset",
+                name, raw
+            );
+            false
+        }
+    }
+}
+
+fn parse_draggable_attribute(value: Option<&str>) -> Draggable {
+    let Some(raw) = value else {
+        return Draggable::Auto;
+    };
+    let lowered = raw.trim().to_ascii_lowercase();
+    match lowered.as_str() {
+        "true" => Draggable::True,
+        "false" => Draggable::False,
+        _ => Draggable::Auto,
+    }
+}
+
+fn parse_spellcheck_attribute(value: Option<&str>) -> SpellCheck {
+    let lowered = value.unwrap_or("").trim().to_ascii_lowercase();
+    match lowered.as_str() {
+        "" | "true" => SpellCheck::True,
+        "false" => SpellCheck::False,
+        _ => SpellCheck::Default,
+    }
+}
+
 impl<Str> Attribute<Str> {
     fn is_multi(&self) -> bool {
         matches!(
@@ -319,6 +768,128 @@ impl<Str> Attribute<Str> {
     }
 }
 
+impl<Str: AsRef<str>> Attribute<Str> {
+    /// Serializes the attribute back to markup, or `None` when it should be left out. Boolean
+    /// attributes follow HTML presence semantics: `true` is emitted bare and `false` is omitted.
+    /// Enumerated ones like `contenteditable` always spell out their state, since a missing
+    /// attribute means the element's default rather than `false`.
+    pub fn to_html(&self) -> Option<String> {
+        match self {
+            Attribute::Id(value) => valued_html("id", Some(value.as_ref())),
+            Attribute::Class(value) => valued_html("class", Some(value.raw.as_ref())),
+            Attribute::Style(value) => valued_html("style", Some(value.raw.as_ref())),
+            Attribute::Title(value) => valued_html("title", Some(value.as_ref())),
+            Attribute::Lang(value) => valued_html("lang", Some(value.as_ref())),
+            Attribute::Dir(value) => valued_html("dir", Some(value.as_ref())),
+            Attribute::Hidden(value) => match value {
+                Hidden::No => None,
+                Hidden::Yes => flag_html("hidden", true),
+                Hidden::UntilFound => valued_html("hidden", Some("until-found")),
+            },
+            Attribute::TabIndex(value) => valued_html("tabindex", Some(value.as_ref())),
+            Attribute::Role(value) => valued_html("role", Some(value.as_ref())),
+            Attribute::AccessKey(value) => valued_html("accesskey", Some(value.as_ref())),
+            Attribute::Draggable(value) => valued_html("draggable", Some(value.as_str())),
+            Attribute::ContentEditable(value) => enumerated_html("contenteditable", *value),
+            Attribute::SpellCheck(value) => valued_html("spellcheck", Some(value.as_str())),
+            Attribute::InputMode(value) => valued_html("inputmode", Some(value.as_ref())),
+            Attribute::EnterKeyHint(value) => valued_html("enterkeyhint", Some(value.as_ref())),
+            Attribute::Translate(value) => {
+                valued_html("translate", Some(if *value { "yes" } else { "no" }))
+            }
+            Attribute::Enabled(value) => flag_html("enabled", *value),
+            Attribute::Disabled(value) => flag_html("disabled", *value),
+            Attribute::Checked(value) => flag_html("checked", *value),
+            Attribute::Selected(value) => flag_html("selected", *value),
+            Attribute::ReadOnly(value) => flag_html("readonly", *value),
+            Attribute::Required(value) => flag_html("required", *value),
+            Attribute::Multiple(value) => flag_html("multiple", *value),
+            Attribute::Autofocus(value) => flag_html("autofocus", *value),
+            Attribute::Inert(value) => flag_html("inert", *value),
+            Attribute::Open(value) => flag_html("open", *value),
+            Attribute::Href(value) => valued_html("href", Some(value.as_ref())),
+            Attribute::Src(value) => valued_html("src", Some(value.as_ref())),
+            Attribute::Alt(value) => valued_html("alt", Some(value.as_ref())),
+            Attribute::Name(value) => valued_html("name", Some(value.as_ref())),
+            Attribute::Value(value) => valued_html("value", Some(value.as_ref())),
+            Attribute::Type(value) => valued_html("type", Some(value.as_ref())),
+            Attribute::Placeholder(value) => valued_html("placeholder", Some(value.as_ref())),
+            Attribute::Min(value) => valued_html("min", Some(value.as_ref())),
+            Attribute::Max(value) => valued_html("max", Some(value.as_ref())),
+            Attribute::Step(value) => valued_html("step", Some(value.as_ref())),
+            Attribute::Width(value) => valued_html("width", Some(value.as_ref())),
+            Attribute::Height(value) => valued_html("height", Some(value.as_ref())),
+            Attribute::Rows(value) => valued_html("rows", Some(value.as_ref())),
+            Attribute::Cols(value) => valued_html("cols", Some(value.as_ref())),
+            Attribute::Size(value) => valued_html("size", Some(value.as_ref())),
+            Attribute::MaxLength(value) => valued_html("maxlength", Some(value.as_ref())),
+            Attribute::MinLength(value) => valued_html("minlength", Some(value.as_ref())),
+            Attribute::Pattern(value) => valued_html("pattern", Some(value.as_ref())),
+            Attribute::Accept(value) => valued_html("accept", Some(value.as_ref())),
+            Attribute::AcceptCharset(value) => valued_html("accept-charset", Some(value.as_ref())),
+            Attribute::AutoComplete(value) => valued_html("autocomplete", Some(value.as_ref())),
+            Attribute::AutoCapitalize(value) => valued_html("autocapitalize", Some(value.as_ref())),
+            Attribute::For(value) => valued_html("for", Some(value.as_ref())),
+            Attribute::Action(value) => valued_html("action", Some(value.as_ref())),
+            Attribute::Method(value) => valued_html("method", Some(value.as_ref())),
+            Attribute::Enctype(value) => valued_html("enctype", Some(value.as_ref())),
+            Attribute::Target(value) => valued_html("target", Some(value.as_ref())),
+            Attribute::Rel(value) => valued_html("rel", Some(value.as_ref())),
+            Attribute::Download(value) => {
+                valued_html("download", value.as_ref().map(AsRef::as_ref))
+            }
+            Attribute::SrcSet(value) => valued_html("srcset", Some(value.as_ref())),
+            Attribute::Sizes(value) => valued_html("sizes", Some(value.as_ref())),
+            Attribute::Media(value) => valued_html("media", Some(value.as_ref())),
+            Attribute::Loading(value) => valued_html("loading", Some(value.as_ref())),
+            Attribute::Decoding(value) => valued_html("decoding", Some(value.as_ref())),
+            Attribute::ReferrerPolicy(value) => valued_html("referrerpolicy", Some(value.as_ref())),
+            Attribute::CrossOrigin(value) => valued_html("crossorigin", Some(value.as_ref())),
+            Attribute::Async(value) => flag_html("async", *value),
+            Attribute::Defer(value) => flag_html("defer", *value),
+            Attribute::Charset(value) => valued_html("charset", Some(value.as_ref())),
+            Attribute::Content(value) => valued_html("content", Some(value.as_ref())),
+            Attribute::HttpEquiv(value) => valued_html("http-equiv", Some(value.as_ref())),
+            Attribute::Controls(value) => flag_html("controls", *value),
+            Attribute::Autoplay(value) => flag_html("autoplay", *value),
+            Attribute::Loop(value) => flag_html("loop", *value),
+            Attribute::Muted(value) => flag_html("muted", *value),
+            Attribute::PlaysInline(value) => flag_html("playsinline", *value),
+            Attribute::Poster(value) => valued_html("poster", Some(value.as_ref())),
+            Attribute::Preload(value) => valued_html("preload", Some(value.as_ref())),
+            Attribute::Start(value) => valued_html("start", Some(value.as_ref())),
+            Attribute::ColSpan(value) => valued_html("colspan", Some(value.as_ref())),
+            Attribute::RowSpan(value) => valued_html("rowspan", Some(value.as_ref())),
+            Attribute::Data { key, value } => valued_html(
+                &format!("data-{}", key.as_ref()),
+                value.as_ref().map(AsRef::as_ref),
+            ),
+            Attribute::Aria { name, value } => valued_html(
+                &format!("aria-{}", name.as_ref()),
+                value.as_ref().map(AsRef::as_ref),
+            ),
+            Attribute::Custom { name, value } => {
+                valued_html(name.as_ref(), value.as_ref().map(AsRef::as_ref))
+            }
+        }
+    }
+}
+
+fn valued_html(name: &str, value: Option<&str>) -> Option<String> {
+    match value {
+        Some(value) => Some(format!("{name}=\"{}\"", escape_attribute(value))),
+        None => Some(name.to_string()),
+    }
+}
+
+fn flag_html(name: &str, present: bool) -> Option<String> {
+    present.then(|| name.to_string())
+}
+
+fn enumerated_html(name: &str, value: bool) -> Option<String> {
+    valued_html(name, Some(if value { "true" } else { "false" }))
+}
+
 impl<'a> Attribute<Cow<'a, str>> {
     pub fn into_owned(self) -> Attribute<String> {
         match self {
@@ -350,6 +921,8 @@ impl<'a> Attribute<Cow<'a, str>> {
             Attribute::Required(value) => Attribute::Required(value),
             Attribute::Multiple(value) => Attribute::Multiple(value),
             Attribute::Autofocus(value) => Attribute::Autofocus(value),
+            Attribute::Inert(value) => Attribute::Inert(value),
+            Attribute::Open(value) => Attribute::Open(value),
             Attribute::Href(value) => Attribute::Href(value.into_owned()),
             Attribute::Src(value) => Attribute::Src(value.into_owned()),
             Attribute::Alt(value) => Attribute::Alt(value.into_owned()),
@@ -398,6 +971,9 @@ impl<'a> Attribute<Cow<'a, str>> {
             Attribute::PlaysInline(value) => Attribute::PlaysInline(value),
             Attribute::Poster(value) => Attribute::Poster(value.into_owned()),
             Attribute::Preload(value) => Attribute::Preload(value.into_owned()),
+            Attribute::Start(value) => Attribute::Start(value.into_owned()),
+            Attribute::ColSpan(value) => Attribute::ColSpan(value.into_owned()),
+            Attribute::RowSpan(value) => Attribute::RowSpan(value.into_owned()),
             Attribute::Aria { name, value } => Attribute::Aria {
                 name: name.into_owned(),
                 value: value.map(Cow::into_owned),
@@ -410,52 +986,228 @@ impl<'a> Attribute<Cow<'a, str>> {
     }
 }
 
-impl<'a> ClassList<Cow<'a, str>> {
-    pub fn parse(raw: Cow<'a, str>) -> Self {
-        match raw {
-            Cow::Borrowed(raw_ref) => {
-                let mut classes = SmallVec::new();
-                for class in raw_ref.split_whitespace() {
-                    if class.is_empty() {
-                        continue;
-                    }
-                    classes.push(Cow::Borrowed(class));
-                }
-                Self {
-                    raw: Cow::Borrowed(raw_ref),
-                    classes,
-                }
+impl<Str> StyleAttribute<Str> {
+    /// Multiplies every `Val::Px` in the parsed declarations by `factor`.
+    pub fn scale_px(&mut self, factor: f32) {
+        for declaration in &mut self.declarations {
+            declaration.scale_px(factor);
+        }
+    }
+
+    /// Clamps every `Val::Px` in the parsed declarations, see [`StyleDeclaration::clamp_px`].
+    pub fn clamp_px(&mut self, max: f32) -> usize {
+        self.declarations
+            .iter_mut()
+            .map(|declaration| declaration.clamp_px(max))
+            .sum()
+    }
+
+    /// Whether the declaration at `index` was marked `!important`.
+    pub fn is_important(&self, index: usize) -> bool {
+        self.important.contains(&index)
+    }
+}
+
+impl StyleAttribute {
+    /// Cascades `higher` over `self`: a declaration in `higher` replaces any of the same property
+    /// in `self`, unless only the one in `self` is `!important`. Properties are compared by
+    /// declaration kind, so a shorthand and its longhands both survive and apply in order.
+    /// Unsupported entries from both sides are kept.
+    pub fn merge(&self, higher: &StyleAttribute) -> StyleAttribute {
+        let mut merged = StyleAttribute {
+            raw: match (self.raw.is_empty(), higher.raw.is_empty()) {
+                (_, true) => self.raw.clone(),
+                (true, false) => higher.raw.clone(),
+                (false, false) => format!("{}; {}", self.raw.trim_end_matches(';'), higher.raw),
+            },
+            declarations: SmallVec::new(),
+            unsupported: self
+                .unsupported
+                .iter()
+                .chain(&higher.unsupported)
+                .cloned()
+                .collect(),
+            important: SmallVec::new(),
+        };
+        for (index, declaration) in self.declarations.iter().enumerate() {
+            let important = self.is_important(index);
+            let overridden = higher
+                .declarations
+                .iter()
+                .enumerate()
+                .any(|(other_index, other)| {
+                    same_property(declaration, other)
+                        && (!important || higher.is_important(other_index))
+                });
+            if !overridden {
+                merged.push_declaration(declaration.clone(), important);
             }
-            Cow::Owned(raw_string) => {
-                let mut classes = SmallVec::new();
-                for class in raw_string.split_whitespace() {
-                    if class.is_empty() {
-                        continue;
-                    }
-                    classes.push(Cow::Owned(class.to_string()));
-                }
-                Self {
-                    raw: Cow::Owned(raw_string),
-                    classes,
-                }
+        }
+        for (index, declaration) in higher.declarations.iter().enumerate() {
+            let important = higher.is_important(index);
+            let blocked = !important
+                && self
+                    .declarations
+                    .iter()
+                    .enumerate()
+                    .any(|(other_index, other)| {
+                        same_property(declaration, other) && self.is_important(other_index)
+                    });
+            if !blocked {
+                merged.push_declaration(declaration.clone(), important);
             }
         }
+        merged
     }
 
-    pub fn into_owned(self) -> ClassList<String> {
-        ClassList {
-            raw: self.raw.into_owned(),
-            classes: self.classes.into_iter().map(Cow::into_owned).collect(),
+    fn push_declaration(&mut self, declaration: StyleDeclaration, important: bool) {
+        if important {
+            self.important.push(self.declarations.len());
+        }
+        self.declarations.push(declaration);
+    }
+}
+
+fn same_property(a: &StyleDeclaration, b: &StyleDeclaration) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+impl StyleDeclaration {
+    /// Multiplies the pixel lengths carried by this declaration; relative units are untouched.
+    pub fn scale_px(&mut self, factor: f32) {
+        self.for_each_px(|px| *px *= factor);
+    }
+
+    /// Limits every pixel length to `-max..=max`, returning how many values had to be clamped.
+    pub fn clamp_px(&mut self, max: f32) -> usize {
+        let mut clamped = 0;
+        self.for_each_px(|px| {
+            if px.abs() > max {
+                *px = px.clamp(-max, max);
+                clamped += 1;
+            }
+        });
+        clamped
+    }
+
+    fn for_each_px(&mut self, mut f: impl FnMut(&mut f32)) {
+        match self {
+            StyleDeclaration::Width(value)
+            | StyleDeclaration::Height(value)
+            | StyleDeclaration::MinWidth(value)
+            | StyleDeclaration::MaxWidth(value)
+            | StyleDeclaration::MinHeight(value)
+            | StyleDeclaration::MaxHeight(value)
+            | StyleDeclaration::Left(value)
+            | StyleDeclaration::Right(value)
+            | StyleDeclaration::Top(value)
+            | StyleDeclaration::Bottom(value)
+            | StyleDeclaration::MarginLeft(value)
+            | StyleDeclaration::MarginRight(value)
+            | StyleDeclaration::MarginTop(value)
+            | StyleDeclaration::MarginBottom(value)
+            | StyleDeclaration::PaddingLeft(value)
+            | StyleDeclaration::PaddingRight(value)
+            | StyleDeclaration::PaddingTop(value)
+            | StyleDeclaration::PaddingBottom(value)
+            | StyleDeclaration::BorderLeft(value)
+            | StyleDeclaration::BorderRight(value)
+            | StyleDeclaration::BorderTop(value)
+            | StyleDeclaration::BorderBottom(value)
+            | StyleDeclaration::RowGap(value)
+            | StyleDeclaration::ColumnGap(value)
+            | StyleDeclaration::FlexBasis(value)
+            | StyleDeclaration::FontSize(value) => px_of_val(value, &mut f),
+            StyleDeclaration::Margin(rect) | StyleDeclaration::Padding(rect) => {
+                px_of_rect(rect, &mut f)
+            }
+            StyleDeclaration::Border(border) => px_of_rect(&mut border.thickness, &mut f),
+            StyleDeclaration::BorderRadius(radius) => {
+                px_of_val(&mut radius.top_left, &mut f);
+                px_of_val(&mut radius.top_right, &mut f);
+                px_of_val(&mut radius.bottom_right, &mut f);
+                px_of_val(&mut radius.bottom_left, &mut f);
+            }
+            StyleDeclaration::Gap { row, column } => {
+                px_of_val(row, &mut f);
+                px_of_val(column, &mut f);
+            }
+            StyleDeclaration::BoxShadow(shadows) => {
+                for shadow in shadows {
+                    px_of_val(&mut shadow.x_offset, &mut f);
+                    px_of_val(&mut shadow.y_offset, &mut f);
+                    px_of_val(&mut shadow.blur_radius, &mut f);
+                    px_of_val(&mut shadow.spread_radius, &mut f);
+                }
+            }
+            StyleDeclaration::Display(_)
+            | StyleDeclaration::BackgroundColor(_)
+            | StyleDeclaration::TextColor(_)
+            | StyleDeclaration::TextTransform(_)
+            | StyleDeclaration::Overflow(_)
+            | StyleDeclaration::OverflowX(_)
+            | StyleDeclaration::OverflowY(_)
+            | StyleDeclaration::AspectRatio(_)
+            | StyleDeclaration::AlignItems(_)
+            | StyleDeclaration::JustifyContent(_)
+            | StyleDeclaration::FlexGrow(_)
+            | StyleDeclaration::FlexShrink(_)
+            | StyleDeclaration::Opacity(_)
+            | StyleDeclaration::LineClamp(_)
+            | StyleDeclaration::Cursor(_)
+            | StyleDeclaration::FontFamily(_)
+            | StyleDeclaration::FlexDirection(_)
+            | StyleDeclaration::FlexWrap(_)
+            | StyleDeclaration::Position(_)
+            | StyleDeclaration::AlignSelf(_)
+            | StyleDeclaration::AlignContent(_)
+            | StyleDeclaration::Transition(_) => {}
         }
     }
 }
 
+fn px_of_val(value: &mut Val, f: &mut impl FnMut(&mut f32)) {
+    if let Val::Px(px) = value {
+        f(px);
+    }
+}
+
+fn px_of_rect(rect: &mut UiRect, f: &mut impl FnMut(&mut f32)) {
+    px_of_val(&mut rect.left, f);
+    px_of_val(&mut rect.right, f);
+    px_of_val(&mut rect.top, f);
+    px_of_val(&mut rect.bottom, f);
+}
+
 impl<'a> StyleAttribute<Cow<'a, str>> {
+    /// Parses an inline `style` value, logging every problem through `warn!`.
     pub fn parse(raw: Cow<'a, str>) -> Self {
-        match raw {
-            Cow::Borrowed(raw_ref) => parse_style_borrowed(raw_ref),
-            Cow::Owned(raw_string) => parse_style_owned(raw_string),
+        Self::parse_with_root_font_size(raw, BASE_FONT_PX)
+    }
+
+    /// Like [`StyleAttribute::parse`], resolving `rem` and `em` lengths against `root_font_px`
+    /// instead of the 16px default.
+    pub fn parse_with_root_font_size(raw: Cow<'a, str>, root_font_px: f32) -> Self {
+        let mut cx = StyleContext::new(root_font_px);
+        let style = Self::parse_with_warnings(raw, &mut cx);
+        for warning in &cx.warnings {
+            warn!("{}", warning);
         }
+        style
+    }
+
+    /// Parses an inline `style` value against `cx`, collecting problems into `cx.warnings` instead
+    /// of logging them.
+    pub fn parse_with_warnings(raw: Cow<'a, str>, cx: &mut StyleContext) -> Self {
+        let mut style = StyleAttribute {
+            raw: Cow::Borrowed(""),
+            declarations: SmallVec::new(),
+            unsupported: SmallVec::new(),
+            important: SmallVec::new(),
+        };
+        parse_declarations(&raw, cx, &mut style);
+        style.raw = raw;
+        style
     }
 
     pub fn into_owned(self) -> StyleAttribute<String> {
@@ -467,6 +1219,7 @@ impl<'a> StyleAttribute<Cow<'a, str>> {
                 .into_iter()
                 .map(UnsupportedStyle::into_owned)
                 .collect(),
+            important: self.important,
         }
     }
 }
@@ -480,97 +1233,60 @@ impl<'a> UnsupportedStyle<Cow<'a, str>> {
     }
 }
 
-fn parse_style_borrowed<'a>(raw: &'a str) -> StyleAttribute<Cow<'a, str>> {
-    let mut declarations = SmallVec::new();
-    let mut unsupported = SmallVec::new();
+/// Parses every declaration of `raw` into `style`, leaving its `raw` field alone.
+fn parse_declarations(raw: &str, cx: &mut StyleContext, style: &mut StyleAttribute<Cow<'_, str>>) {
     let mut push_unsupported = |property: &str, value: &str| {
-        unsupported.push(UnsupportedStyle {
+        style.unsupported.push(UnsupportedStyle {
             property: Cow::Owned(property.to_string()),
             value: Cow::Owned(value.to_string()),
         });
     };
-    for declaration in raw.split(';') {
-        let trimmed = declaration.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        let Some((name_raw, value_raw)) = trimmed.split_once(':') else {
-            warn!("style declaration missing ':' -> {:?}", trimmed);
-            push_unsupported(trimmed, "");
-            continue;
+    for declaration in split_declarations(raw) {
+        let declaration = match declaration {
+            Ok(declaration) => declaration,
+            Err(DeclarationError::MissingColon(text)) => {
+                push_warning(
+                    &mut cx.warnings,
+                    text,
+                    "",
+                    format!("style declaration missing ':' -> {:?}", text),
+                );
+                push_unsupported(text, "");
+                continue;
+            }
+            Err(DeclarationError::MissingProperty(text)) => {
+                push_warning(
+                    &mut cx.warnings,
+                    "",
+                    text,
+                    format!("style declaration missing property name -> {:?}", text),
+                );
+                continue;
+            }
+            Err(DeclarationError::MissingValue(property)) => {
+                push_warning(
+                    &mut cx.warnings,
+                    property,
+                    "",
+                    format!("style declaration missing value for '{}'", property),
+                );
+                push_unsupported(property, "");
+                continue;
+            }
         };
-        let name_raw = name_raw.trim();
-        let mut value_raw = value_raw.trim();
-        if name_raw.is_empty() {
-            warn!("style declaration missing property name -> {:?}", trimmed);
-            continue;
-        }
-        value_raw = strip_important(value_raw);
-        if value_raw.is_empty() {
-            warn!("style declaration missing value for '{}'", name_raw);
-            push_unsupported(name_raw, value_raw);
-            continue;
-        }
-        let name_lower = name_raw.to_ascii_lowercase();
+        let name_lower = declaration.property.to_ascii_lowercase();
+        let first = style.declarations.len();
         parse_style_property(
-            name_raw,
+            declaration.property,
             &name_lower,
-            value_raw,
-            &mut declarations,
+            declaration.value,
+            &mut style.declarations,
             &mut push_unsupported,
+            cx,
         );
-    }
-    StyleAttribute {
-        raw: Cow::Borrowed(raw),
-        declarations,
-        unsupported,
-    }
-}
-
-fn parse_style_owned<'a>(raw: String) -> StyleAttribute<Cow<'a, str>> {
-    let mut declarations = SmallVec::new();
-    let mut unsupported = SmallVec::new();
-    let mut push_unsupported = |property: &str, value: &str| {
-        unsupported.push(UnsupportedStyle {
-            property: Cow::Owned(property.to_string()),
-            value: Cow::Owned(value.to_string()),
-        });
-    };
-    for declaration in raw.split(';') {
-        let trimmed = declaration.trim();
-        if trimmed.is_empty() {
-            continue;
+        if declaration.important {
+            style.important.extend(first..style.declarations.len());
         }
-        let Some((name_raw, value_raw)) = trimmed.split_once(':') else {
-            warn!("style declaration missing ':' -> {:?}", trimmed);
-            push_unsupported(trimmed, "");
-            continue;
-        };
-        let name_raw = name_raw.trim();
-        let mut value_raw = value_raw.trim();
-        if name_raw.is_empty() {
-            warn!("style declaration missing property name -> {:?}", trimmed);
-            continue;
-        }
-        value_raw = strip_important(value_raw);
-        if value_raw.is_empty() {
-            warn!("style declaration missing value for '{}'", name_raw);
-            push_unsupported(name_raw, value_raw);
-            continue;
-        }
-        let name_lower = name_raw.to_ascii_lowercase();
-        parse_style_property(
-            name_raw,
-            &name_lower,
-            value_raw,
-            &mut declarations,
-            &mut push_unsupported,
-        );
-    }
-    StyleAttribute {
-        raw: Cow::Owned(raw),
-        declarations,
-        unsupported,
     }
 }
 
@@ -580,149 +1296,190 @@ fn parse_style_property<F>(
     value: &str,
     declarations: &mut SmallVec<[StyleDeclaration; 8]>,
     push_unsupported: &mut F,
+    cx: &mut StyleContext,
 ) where
     F: FnMut(&str, &str),
 {
     match name_lower {
-        "width" => apply_val_property(
+        "width" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::Width,
         ),
-        "display" => apply_display_property(name_raw, value, declarations, push_unsupported),
-        "height" => apply_val_property(
+        "display" => apply_display_property(name_raw, value, declarations, push_unsupported, cx),
+        "height" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::Height,
         ),
-        "min-width" => apply_val_property(
+        "min-width" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::MinWidth,
         ),
-        "max-width" => apply_val_property(
+        "max-width" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::MaxWidth,
         ),
-        "min-height" => apply_val_property(
+        "min-height" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::MinHeight,
         ),
-        "max-height" => apply_val_property(
+        "max-height" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::MaxHeight,
         ),
-        "left" => apply_val_property(
+        "left" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::Left,
         ),
-        "right" => apply_val_property(
+        "right" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::Right,
         ),
-        "top" => apply_val_property(
+        "top" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::Top,
         ),
-        "bottom" => apply_val_property(
+        "bottom" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::Bottom,
         ),
-        "margin" => apply_rect_property(
+        "margin" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_ui_rect,
             StyleDeclaration::Margin,
         ),
-        "margin-left" => apply_val_property(
+        "margin-left" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::MarginLeft,
         ),
-        "margin-right" => apply_val_property(
+        "margin-right" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::MarginRight,
         ),
-        "margin-top" => apply_val_property(
+        "margin-top" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::MarginTop,
         ),
-        "margin-bottom" => apply_val_property(
+        "margin-bottom" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::MarginBottom,
         ),
-        "padding" => apply_rect_property(
+        "padding" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_ui_rect,
             StyleDeclaration::Padding,
         ),
-        "padding-left" => apply_val_property(
+        "padding-left" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::PaddingLeft,
         ),
-        "padding-right" => apply_val_property(
+        "padding-right" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::PaddingRight,
         ),
-        "padding-top" => apply_val_property(
+        "padding-top" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::PaddingTop,
         ),
-        "padding-bottom" => apply_val_property(
+        "padding-bottom" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::PaddingBottom,
         ),
         "border" => apply_border_shorthand(
@@ -730,6 +1487,7 @@ fn parse_style_property<F>(
             value,
             declarations,
             push_unsupported,
+            cx,
             BorderTarget::All,
         ),
         "border-left" => apply_border_shorthand(
@@ -737,6 +1495,7 @@ fn parse_style_property<F>(
             value,
             declarations,
             push_unsupported,
+            cx,
             BorderTarget::Left,
         ),
         "border-right" => apply_border_shorthand(
@@ -744,6 +1503,7 @@ fn parse_style_property<F>(
             value,
             declarations,
             push_unsupported,
+            cx,
             BorderTarget::Right,
         ),
         "border-top" => apply_border_shorthand(
@@ -751,6 +1511,7 @@ fn parse_style_property<F>(
             value,
             declarations,
             push_unsupported,
+            cx,
             BorderTarget::Top,
         ),
         "border-bottom" => apply_border_shorthand(
@@ -758,208 +1519,411 @@ fn parse_style_property<F>(
             value,
             declarations,
             push_unsupported,
+            cx,
             BorderTarget::Bottom,
         ),
-        "border-width" => apply_border_width(name_raw, value, declarations, push_unsupported),
-        "border-left-width" => apply_border_side_width(
+        "border-width" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
-            BorderTarget::Left,
+            cx,
+            parse_border_width,
+            |thickness| StyleDeclaration::Border(BorderStyle { thickness }),
         ),
-        "border-right-width" => apply_border_side_width(
+        "border-left-width" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
-            BorderTarget::Right,
+            cx,
+            parse_length,
+            |width| BorderTarget::Left.declaration(width),
         ),
-        "border-top-width" => apply_border_side_width(
+        "border-right-width" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
-            BorderTarget::Top,
+            cx,
+            parse_length,
+            |width| BorderTarget::Right.declaration(width),
         ),
-        "border-bottom-width" => apply_border_side_width(
+        "border-top-width" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
-            BorderTarget::Bottom,
+            cx,
+            parse_length,
+            |width| BorderTarget::Top.declaration(width),
+        ),
+        "border-bottom-width" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            parse_length,
+            |width| BorderTarget::Bottom.declaration(width),
+        ),
+        "border-radius" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            parse_border_radius,
+            StyleDeclaration::BorderRadius,
         ),
-        "border-radius" => apply_border_radius(name_raw, value, declarations, push_unsupported),
-        "background-color" => apply_color_property(
+        "background-color" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            |value, _| parse_color(value),
             StyleDeclaration::BackgroundColor,
         ),
-        "align-items" => {
-            apply_align_items_property(name_raw, value, declarations, push_unsupported)
-        }
-        "justify-content" => {
-            apply_justify_content_property(name_raw, value, declarations, push_unsupported)
+        "color" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_color(value),
+            StyleDeclaration::TextColor,
+        ),
+        "box-shadow" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            parse_box_shadow,
+            StyleDeclaration::BoxShadow,
+        ),
+        "background" => {
+            apply_background_shorthand(name_raw, value, declarations, push_unsupported, cx)
         }
-        "row-gap" => apply_val_property(
+        "align-items" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_align_items(value),
+            StyleDeclaration::AlignItems,
+        ),
+        "justify-content" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_justify_content(value),
+            StyleDeclaration::JustifyContent,
+        ),
+        "row-gap" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::RowGap,
         ),
-        "column-gap" => apply_val_property(
+        "column-gap" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::ColumnGap,
         ),
-        "gap" => apply_gap(name_raw, value, declarations, push_unsupported),
-        "flex-basis" => apply_val_property(
+        "gap" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            parse_gap,
+            |(row, column)| StyleDeclaration::Gap { row, column },
+        ),
+        "flex-basis" => apply_parsed(
             name_raw,
             value,
             declarations,
             push_unsupported,
+            cx,
+            parse_val,
             StyleDeclaration::FlexBasis,
         ),
-        _ => {
-            warn!("unsupported style property '{}'", name_raw);
-            push_unsupported(name_raw, value);
-        }
-    }
-}
-
-fn apply_val_property<F>(
-    name: &str,
-    value: &str,
-    declarations: &mut SmallVec<[StyleDeclaration; 8]>,
-    push_unsupported: &mut F,
-    map: fn(Val) -> StyleDeclaration,
-) where
-    F: FnMut(&str, &str),
-{
-    match parse_val(value) {
-        Ok(val) => {
-            declarations.push(map(val));
-        }
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
+        "aspect-ratio" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_aspect_ratio(value),
+            StyleDeclaration::AspectRatio,
+        ),
+        "flex-grow" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_flex_factor(value),
+            StyleDeclaration::FlexGrow,
+        ),
+        "flex-shrink" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_flex_factor(value),
+            StyleDeclaration::FlexShrink,
+        ),
+        "font-size" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            parse_font_size,
+            StyleDeclaration::FontSize,
+        ),
+        "font-family" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_font_family(value),
+            StyleDeclaration::FontFamily,
+        ),
+        "flex-direction" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_flex_direction(value),
+            StyleDeclaration::FlexDirection,
+        ),
+        "flex-wrap" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_flex_wrap(value),
+            StyleDeclaration::FlexWrap,
+        ),
+        "text-transform" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_text_transform(value),
+            StyleDeclaration::TextTransform,
+        ),
+        "position" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_position(value),
+            StyleDeclaration::Position,
+        ),
+        "overflow" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_overflow(value),
+            StyleDeclaration::Overflow,
+        ),
+        "overflow-x" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_overflow_axis(value),
+            StyleDeclaration::OverflowX,
+        ),
+        "overflow-y" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_overflow_axis(value),
+            StyleDeclaration::OverflowY,
+        ),
+        "align-self" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_align_self(value),
+            StyleDeclaration::AlignSelf,
+        ),
+        "align-content" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_align_content(value),
+            StyleDeclaration::AlignContent,
+        ),
+        "transition" => {
+            apply_transition_property(name_raw, value, declarations, push_unsupported, cx)
         }
-    }
-}
-
-fn apply_display_property<F>(
-    name: &str,
-    value: &str,
-    declarations: &mut SmallVec<[StyleDeclaration; 8]>,
-    push_unsupported: &mut F,
-) where
-    F: FnMut(&str, &str),
-{
-    match parse_display(value) {
-        Ok(display) => declarations.push(StyleDeclaration::Display(display)),
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
+        "opacity" => apply_opacity_property(name_raw, value, declarations, push_unsupported, cx),
+        "cursor" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_cursor(value),
+            StyleDeclaration::Cursor,
+        ),
+        "line-clamp" | "-webkit-line-clamp" => apply_parsed(
+            name_raw,
+            value,
+            declarations,
+            push_unsupported,
+            cx,
+            |value, _| parse_line_clamp(value),
+            StyleDeclaration::LineClamp,
+        ),
+        _ => {
+            push_warning(
+                &mut cx.warnings,
+                name_raw,
+                value,
+                format!("unsupported style property '{}'", name_raw),
             );
-            push_unsupported(name, value);
+            push_unsupported(name_raw, value);
         }
     }
 }
 
-fn apply_align_items_property<F>(
+/// Parses `value` with `parse`, handing it the root font size, and pushes the declaration `ctor`
+/// builds from the result. A value that fails to parse is reported through [`reject_value`].
+fn apply_parsed<T, F>(
     name: &str,
     value: &str,
     declarations: &mut SmallVec<[StyleDeclaration; 8]>,
     push_unsupported: &mut F,
+    cx: &mut StyleContext,
+    parse: impl FnOnce(&str, f32) -> Result<T, StyleParseError>,
+    ctor: impl FnOnce(T) -> StyleDeclaration,
 ) where
     F: FnMut(&str, &str),
 {
-    match parse_align_items(value) {
-        Ok(align_items) => declarations.push(StyleDeclaration::AlignItems(align_items)),
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
-        }
+    match parse(value, cx.root_font_px) {
+        Ok(parsed) => declarations.push(ctor(parsed)),
+        Err(err) => reject_value(name, value, &err, push_unsupported, cx),
     }
 }
 
-fn apply_justify_content_property<F>(
+/// Records a declaration whose value could not be parsed as both a warning and an unsupported
+/// entry.
+fn reject_value<F>(
     name: &str,
     value: &str,
-    declarations: &mut SmallVec<[StyleDeclaration; 8]>,
+    err: &StyleParseError,
     push_unsupported: &mut F,
+    cx: &mut StyleContext,
 ) where
     F: FnMut(&str, &str),
 {
-    match parse_justify_content(value) {
-        Ok(justify_content) => {
-            declarations.push(StyleDeclaration::JustifyContent(justify_content));
-        }
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
-        }
-    }
+    push_warning(
+        &mut cx.warnings,
+        name,
+        value,
+        format!(
+            "unsupported style value for '{}': {:?} ({})",
+            name, value, err
+        ),
+    );
+    push_unsupported(name, value);
 }
 
-fn apply_rect_property<F>(
+fn apply_display_property<F>(
     name: &str,
     value: &str,
     declarations: &mut SmallVec<[StyleDeclaration; 8]>,
     push_unsupported: &mut F,
-    map: fn(UiRect) -> StyleDeclaration,
+    cx: &mut StyleContext,
 ) where
     F: FnMut(&str, &str),
 {
-    match parse_ui_rect(value) {
-        Ok(rect) => {
-            declarations.push(map(rect));
-        }
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
+    match parse_display(value) {
+        Ok(display) => {
+            declarations.push(StyleDeclaration::Display(display));
+            // Bevy UI has no inline formatting context, so these lay out as their block-level
+            // counterparts. The original keyword stays in `unsupported` for tooling to report.
+            if is_inline_display(value) {
+                push_warning(
+                    &mut cx.warnings,
+                    name,
+                    value,
+                    format!(
+                        "'{}: {}' is laid out as '{:?}'; its inline behavior is not supported",
+                        name,
+                        value.trim(),
+                        display
+                    ),
+                );
+                push_unsupported(name, value);
+            }
         }
+        Err(err) => reject_value(name, value, &err, push_unsupported, cx),
     }
 }
 
-fn apply_color_property<F>(
+fn apply_opacity_property<F>(
     name: &str,
     value: &str,
     declarations: &mut SmallVec<[StyleDeclaration; 8]>,
     push_unsupported: &mut F,
-    map: fn(Color) -> StyleDeclaration,
+    cx: &mut StyleContext,
 ) where
     F: FnMut(&str, &str),
 {
-    match parse_color(value) {
-        Ok(color) => {
-            declarations.push(map(color));
-        }
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
+    match parse_unit_float(value, 0.0, 1.0) {
+        Ok(opacity) => {
+            declarations.push(StyleDeclaration::Opacity(opacity));
+            // Bevy UI has no node-level opacity, so only the background is faded. The keyword
+            // stays in `unsupported` for tooling to report, like an inline display.
+            if opacity < 1.0 {
+                push_warning(
+                    &mut cx.warnings,
+                    name,
+                    value,
+                    format!(
+                        "'{}: {}' only fades the background; text and children stay opaque",
+                        name,
+                        value.trim()
+                    ),
+                );
+                push_unsupported(name, value);
+            }
         }
+        Err(err) => reject_value(name, value, &err, push_unsupported, cx),
     }
 }
 
@@ -972,140 +1936,142 @@ enum BorderTarget {
     Bottom,
 }
 
+impl BorderTarget {
+    fn declaration(self, width: Val) -> StyleDeclaration {
+        match self {
+            BorderTarget::All => StyleDeclaration::Border(BorderStyle {
+                thickness: UiRect::all(width),
+            }),
+            BorderTarget::Left => StyleDeclaration::BorderLeft(width),
+            BorderTarget::Right => StyleDeclaration::BorderRight(width),
+            BorderTarget::Top => StyleDeclaration::BorderTop(width),
+            BorderTarget::Bottom => StyleDeclaration::BorderBottom(width),
+        }
+    }
+}
+
 fn apply_border_shorthand<F>(
     name: &str,
     value: &str,
     declarations: &mut SmallVec<[StyleDeclaration; 8]>,
     push_unsupported: &mut F,
+    cx: &mut StyleContext,
     target: BorderTarget,
 ) where
     F: FnMut(&str, &str),
 {
-    match parse_border_width_shorthand(value) {
+    match parse_border_width_shorthand(value, cx.root_font_px) {
         Ok(parsed) => {
-            match target {
-                BorderTarget::All => declarations.push(StyleDeclaration::Border(BorderStyle {
-                    thickness: UiRect::all(parsed.width),
-                })),
-                BorderTarget::Left => declarations.push(StyleDeclaration::BorderLeft(parsed.width)),
-                BorderTarget::Right => {
-                    declarations.push(StyleDeclaration::BorderRight(parsed.width))
-                }
-                BorderTarget::Top => declarations.push(StyleDeclaration::BorderTop(parsed.width)),
-                BorderTarget::Bottom => {
-                    declarations.push(StyleDeclaration::BorderBottom(parsed.width))
-                }
-            }
+            declarations.push(target.declaration(parsed.width));
             if parsed.has_extras {
-                warn!("unsupported extra tokens in '{}': {:?}", name, value);
+                push_warning(
+                    &mut cx.warnings,
+                    name,
+                    value,
+                    format!("unsupported extra tokens in '{}': {:?}", name, value),
+                );
                 push_unsupported(name, value);
             }
         }
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
-        }
+        Err(err) => reject_value(name, value, &err, push_unsupported, cx),
     }
 }
 
-fn apply_border_width<F>(
+fn apply_transition_property<F>(
     name: &str,
     value: &str,
     declarations: &mut SmallVec<[StyleDeclaration; 8]>,
     push_unsupported: &mut F,
+    cx: &mut StyleContext,
 ) where
     F: FnMut(&str, &str),
 {
-    match parse_ui_rect(value) {
-        Ok(rect) => {
-            declarations.push(StyleDeclaration::Border(BorderStyle { thickness: rect }));
-        }
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
+    match parse_transition(value) {
+        Ok(parsed) => {
+            declarations.push(StyleDeclaration::Transition(parsed.spec));
+            if parsed.has_extras {
+                push_warning(
+                    &mut cx.warnings,
+                    name,
+                    value,
+                    format!(
+                        "only the first transition in '{}' is supported: {:?}",
+                        name, value
+                    ),
+                );
+                push_unsupported(name, value);
+            }
         }
+        Err(err) => reject_value(name, value, &err, push_unsupported, cx),
     }
 }
 
-fn apply_border_side_width<F>(
+fn apply_background_shorthand<F>(
     name: &str,
     value: &str,
     declarations: &mut SmallVec<[StyleDeclaration; 8]>,
     push_unsupported: &mut F,
-    target: BorderTarget,
+    cx: &mut StyleContext,
 ) where
     F: FnMut(&str, &str),
 {
-    match parse_val(value) {
-        Ok(val) => match target {
-            BorderTarget::Left => declarations.push(StyleDeclaration::BorderLeft(val)),
-            BorderTarget::Right => declarations.push(StyleDeclaration::BorderRight(val)),
-            BorderTarget::Top => declarations.push(StyleDeclaration::BorderTop(val)),
-            BorderTarget::Bottom => declarations.push(StyleDeclaration::BorderBottom(val)),
-            BorderTarget::All => declarations.push(StyleDeclaration::Border(BorderStyle {
-                thickness: UiRect::all(val),
-            })),
-        },
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
+    match parse_background_shorthand(value) {
+        Ok(parsed) => {
+            declarations.push(StyleDeclaration::BackgroundColor(parsed.color));
+            if parsed.has_extras {
+                push_warning(
+                    &mut cx.warnings,
+                    name,
+                    value,
+                    format!("unsupported extra tokens in '{}': {:?}", name, value),
+                );
+                push_unsupported(name, value);
+            }
         }
+        Err(err) => reject_value(name, value, &err, push_unsupported, cx),
     }
 }
 
-fn apply_border_radius<F>(
-    name: &str,
-    value: &str,
-    declarations: &mut SmallVec<[StyleDeclaration; 8]>,
-    push_unsupported: &mut F,
-) where
-    F: FnMut(&str, &str),
-{
-    match parse_border_radius(value) {
-        Ok(radius) => {
-            declarations.push(StyleDeclaration::BorderRadius(radius));
-        }
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
-        }
+/// `border-width` takes one to four lengths like `margin`, none of them a percentage.
+fn parse_border_width(value: &str, root_font_px: f32) -> Result<UiRect, StyleParseError> {
+    let rect = parse_ui_rect(value, root_font_px)?;
+    for side in [rect.left, rect.right, rect.top, rect.bottom] {
+        reject_percent(side)?;
     }
+    Ok(rect)
 }
 
-fn apply_gap<F>(
-    name: &str,
-    value: &str,
-    declarations: &mut SmallVec<[StyleDeclaration; 8]>,
-    push_unsupported: &mut F,
-) where
-    F: FnMut(&str, &str),
-{
-    match parse_gap(value) {
-        Ok((row, column)) => {
-            declarations.push(StyleDeclaration::Gap { row, column });
-        }
-        Err(err) => {
-            warn!(
-                "unsupported style value for '{}': {:?} ({})",
-                name, value, err
-            );
-            push_unsupported(name, value);
+fn parse_font_size(value: &str, root_font_px: f32) -> Result<Val, StyleParseError> {
+    let trimmed = value.trim();
+    match parse_val(trimmed, root_font_px)? {
+        Val::Auto => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+        Val::Px(number) | Val::Percent(number) if number < 0.0 => {
+            Err(StyleParseError::InvalidNumber)
         }
+        size => Ok(size),
+    }
+}
+
+fn parse_line_clamp(value: &str) -> Result<u32, StyleParseError> {
+    match value.trim().parse::<u32>() {
+        Ok(lines) if lines > 0 => Ok(lines),
+        _ => Err(StyleParseError::InvalidNumber),
     }
 }
 
+pub(crate) fn push_warning(
+    warnings: &mut Vec<StyleWarning>,
+    property: &str,
+    value: &str,
+    message: String,
+) {
+    warnings.push(StyleWarning {
+        property: property.to_string(),
+        value: value.to_string(),
+        message,
+    });
+}
+
 #[derive(Debug)]
 enum StyleParseError {
     Empty,
@@ -1113,10 +2079,13 @@ enum StyleParseError {
     InvalidColor(String),
     InvalidKeyword(String),
     UnsupportedUnit(String),
+    MixedUnits(String),
+    PercentNotAllowed,
     WrongArity {
         expected: &'static str,
         found: usize,
     },
+    InvalidCalc(String),
 }
 
 impl std::fmt::Display for StyleParseError {
@@ -1133,23 +2102,21 @@ impl std::fmt::Display for StyleParseError {
             StyleParseError::UnsupportedUnit(unit) => {
                 write!(f, "unsupported unit '{}'", unit)
             }
+            StyleParseError::MixedUnits(value) => {
+                write!(f, "arguments of '{}' do not share a unit", value)
+            }
+            StyleParseError::PercentNotAllowed => {
+                write!(f, "percentages are not allowed here")
+            }
             StyleParseError::WrongArity { expected, found } => {
                 write!(f, "expected {}, found {}", expected, found)
             }
+            StyleParseError::InvalidCalc(reason) => write!(f, "invalid calc(): {}", reason),
         }
     }
 }
 
-fn strip_important(value: &str) -> &str {
-    let trimmed = value.trim();
-    if let Some(stripped) = trimmed.strip_suffix("!important") {
-        stripped.trim_end()
-    } else {
-        trimmed
-    }
-}
-
-fn parse_val(value: &str) -> Result<Val, StyleParseError> {
+fn parse_val(value: &str, root_font_px: f32) -> Result<Val, StyleParseError> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
         return Err(StyleParseError::Empty);
@@ -1157,9 +2124,22 @@ fn parse_val(value: &str) -> Result<Val, StyleParseError> {
     if trimmed.eq_ignore_ascii_case("auto") {
         return Ok(Val::Auto);
     }
+    if let Some(result) = parse_calc_function(trimmed, root_font_px) {
+        return result;
+    }
+    if let Some(result) = parse_comparison_function(trimmed, root_font_px) {
+        return result;
+    }
     if let Some(number) = trimmed.strip_suffix("px") {
         return Ok(Val::Px(parse_number(number)?));
     }
+    // See `ParseSettings::root_font_size` for why `em` means `rem`.
+    if let Some(number) = trimmed
+        .strip_suffix("rem")
+        .or_else(|| trimmed.strip_suffix("em"))
+    {
+        return Ok(Val::Px(parse_number(number)? * root_font_px));
+    }
     if let Some(number) = trimmed.strip_suffix('%') {
         return Ok(Val::Percent(parse_number(number)?));
     }
@@ -1188,64 +2168,602 @@ fn parse_val(value: &str) -> Result<Val, StyleParseError> {
     Err(StyleParseError::UnsupportedUnit(unit.to_string()))
 }
 
-fn parse_color(value: &str) -> Result<Color, StyleParseError> {
-    let trimmed = value.trim();
-    if trimmed.is_empty() {
-        return Err(StyleParseError::Empty);
+/// A length for a property where CSS has no percentages, like border widths and shadow offsets.
+/// Bevy would resolve a `Val::Percent` against the parent anyway, so it is refused rather than
+/// laid out in a way no browser would.
+fn parse_length(value: &str, root_font_px: f32) -> Result<Val, StyleParseError> {
+    parse_val(value, root_font_px).and_then(reject_percent)
+}
+
+fn reject_percent(value: Val) -> Result<Val, StyleParseError> {
+    match value {
+        Val::Percent(_) => Err(StyleParseError::PercentNotAllowed),
+        _ => Ok(value),
     }
-    let lowered = trimmed.to_ascii_lowercase();
-    if lowered == "transparent" {
-        return Ok(Color::NONE);
+}
+
+/// Folds `min()`, `max()` and `clamp()` to a single value when every argument shares a unit, as
+/// there is no way to express a comparison across units in a `Val`. `None` for other values.
+fn parse_comparison_function(
+    value: &str,
+    root_font_px: f32,
+) -> Option<Result<Val, StyleParseError>> {
+    let open = value.find('(')?;
+    let name = value[..open].trim().to_ascii_lowercase();
+    if !matches!(name.as_str(), "min" | "max" | "clamp") {
+        return None;
     }
-    if let Ok(srgba) = Srgba::hex(trimmed) {
-        return Ok(Color::from(srgba));
+    let Some(args) = value[open + 1..].strip_suffix(')') else {
+        return Some(Err(StyleParseError::InvalidNumber));
+    };
+    let values = match split_top_level(args, |ch| ch == ',')
+        .into_iter()
+        .map(|arg| parse_val(arg, root_font_px))
+        .collect::<Result<SmallVec<[Val; 3]>, _>>()
+    {
+        Ok(values) => values,
+        Err(err) => return Some(Err(err)),
+    };
+    let Some(first) = values.first().copied() else {
+        return Some(Err(StyleParseError::Empty));
+    };
+    let mut numbers = SmallVec::<[f32; 3]>::new();
+    for arg in &values {
+        match val_number(*arg) {
+            Some(number) if std::mem::discriminant(arg) == std::mem::discriminant(&first) => {
+                numbers.push(number)
+            }
+            _ => return Some(Err(StyleParseError::MixedUnits(value.to_string()))),
+        }
     }
-    let color = match lowered.as_str() {
-        "black" => basic::BLACK,
-        "silver" => basic::SILVER,
-        "gray" | "grey" => basic::GRAY,
-        "white" => basic::WHITE,
-        "maroon" => basic::MAROON,
-        "red" => basic::RED,
-        "purple" => basic::PURPLE,
-        "fuchsia" => basic::FUCHSIA,
-        "green" => basic::GREEN,
-        "lime" => basic::LIME,
-        "olive" => basic::OLIVE,
-        "yellow" => basic::YELLOW,
-        "navy" => basic::NAVY,
-        "blue" => basic::BLUE,
-        "teal" => basic::TEAL,
-        "aqua" => basic::AQUA,
-        _ => return Err(StyleParseError::InvalidColor(trimmed.to_string())),
+    let folded = match (name.as_str(), numbers.as_slice()) {
+        ("min", _) => numbers.iter().copied().fold(f32::INFINITY, f32::min),
+        ("max", _) => numbers.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+        // Like CSS, a minimum above the maximum wins rather than panicking as `f32::clamp` would.
+        ("clamp", [min, preferred, max]) => min.max(preferred.min(*max)),
+        _ => {
+            return Some(Err(StyleParseError::WrongArity {
+                expected: "3 arguments",
+                found: numbers.len(),
+            }))
+        }
     };
-    Ok(Color::from(color))
+    Some(Ok(with_val_number(first, folded)))
 }
 
-fn parse_display(value: &str) -> Result<Display, StyleParseError> {
-    let trimmed = value.trim();
-    if trimmed.is_empty() {
-        return Err(StyleParseError::Empty);
-    }
-    let lowered = trimmed.to_ascii_lowercase();
-    match lowered.as_str() {
-        "flex" => Ok(Display::Flex),
-        "grid" => Ok(Display::Grid),
-        "block" => Ok(Display::Block),
-        "none" => Ok(Display::None),
-        "inline-flex" => Ok(Display::Flex),
-        "inline-grid" => Ok(Display::Grid),
-        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+/// Evaluates `calc(...)` with `+`, `-`, `*`, `/` and nested parentheses or `calc()`s. A `Val` holds
+/// a single unit, so the expression has to reduce to one. Supported combinations:
+///
+/// - length `+`/`-` length of the same unit, e.g. `calc(10px + 2rem)`, where `rem` and `em`
+///   count as `px`;
+/// - number `*` length, length `*` number and length `/` non-zero number, e.g. `calc(100% / 3)`;
+/// - numbers with each other, a bare result counting as `px`;
+/// - nesting, e.g. `calc((2rem + 4px) * 2)` or `calc(2 * calc(1px + 1px))`.
+///
+/// Mixing units, like `calc(100% - 20px)`, adding a number to a length, multiplying two lengths
+/// and dividing by a length or zero are refused with an explanation.
+fn parse_calc_function(value: &str, root_font_px: f32) -> Option<Result<Val, StyleParseError>> {
+    let open = value.find('(')?;
+    if !value[..open].trim().eq_ignore_ascii_case("calc") {
+        return None;
     }
+    let mut parser = CalcParser {
+        rest: &value[open..],
+        root_font_px,
+        depth: 0,
+    };
+    let result = parser.factor().and_then(|result| {
+        if parser.rest.trim().is_empty() {
+            Ok(result)
+        } else {
+            Err(calc_error(format!("unexpected '{}'", parser.rest.trim())))
+        }
+    });
+    Some(result.map(|result| match result {
+        // Like other unitless lengths in inline styles, a bare number counts as pixels.
+        CalcValue::Number(number) => Val::Px(number),
+        CalcValue::Length(length) => length,
+    }))
 }
 
-fn parse_align_items(value: &str) -> Result<AlignItems, StyleParseError> {
-    let trimmed = value.trim();
-    if trimmed.is_empty() {
-        return Err(StyleParseError::Empty);
-    }
-    let lowered = trimmed.to_ascii_lowercase();
-    match lowered.as_str() {
+#[derive(Clone, Copy)]
+enum CalcValue {
+    Number(f32),
+    Length(Val),
+}
+
+/// How deeply parentheses, nested `calc()`s and unary minuses may nest before a `calc()` is
+/// refused; the parser recurses once per level, so unbounded input could overflow the stack.
+const MAX_CALC_DEPTH: usize = 32;
+
+struct CalcParser<'a> {
+    rest: &'a str,
+    root_font_px: f32,
+    /// Number of factors currently being parsed, see [`MAX_CALC_DEPTH`].
+    depth: usize,
+}
+
+impl CalcParser<'_> {
+    fn expression(&mut self) -> Result<CalcValue, StyleParseError> {
+        let mut left = self.term()?;
+        while let Some(operator) = self.eat_any(&['+', '-']) {
+            let right = self.term()?;
+            left = calc_add(left, right, operator == '-')?;
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<CalcValue, StyleParseError> {
+        let mut left = self.factor()?;
+        while let Some(operator) = self.eat_any(&['*', '/']) {
+            let right = self.factor()?;
+            left = if operator == '*' {
+                calc_multiply(left, right)?
+            } else {
+                calc_divide(left, right)?
+            };
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<CalcValue, StyleParseError> {
+        if self.depth == MAX_CALC_DEPTH {
+            return Err(calc_error("nested too deeply".to_string()));
+        }
+        self.depth += 1;
+        let factor = self.nested_factor();
+        self.depth -= 1;
+        factor
+    }
+
+    fn nested_factor(&mut self) -> Result<CalcValue, StyleParseError> {
+        self.rest = self.rest.trim_start();
+        if let Some(rest) = self.rest.strip_prefix('-')
+            && !rest.starts_with(|ch: char| ch.is_ascii_digit() || ch == '.')
+        {
+            self.rest = rest;
+            return calc_multiply(CalcValue::Number(-1.0), self.factor()?);
+        }
+        let nested = self
+            .rest
+            .get(..5)
+            .is_some_and(|head| head.eq_ignore_ascii_case("calc("));
+        if nested {
+            self.rest = &self.rest[4..];
+        }
+        if self.eat_any(&['(']).is_some() {
+            let inner = self.expression()?;
+            if self.eat_any(&[')']).is_none() {
+                return Err(calc_error("missing ')'".to_string()));
+            }
+            return Ok(inner);
+        }
+        let end = self
+            .rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, ch)| !(ch.is_ascii_alphanumeric() || matches!(ch, '.' | '%')))
+            .map_or(self.rest.len(), |(index, _)| index);
+        let (token, rest) = self.rest.split_at(end);
+        if token.is_empty() {
+            return Err(calc_error("missing a value".to_string()));
+        }
+        self.rest = rest;
+        if let Ok(number) = token.parse::<f32>() {
+            return Ok(CalcValue::Number(number));
+        }
+        match parse_val(token, self.root_font_px)? {
+            Val::Auto => Err(calc_error("'auto' is not a length".to_string())),
+            length => Ok(CalcValue::Length(length)),
+        }
+    }
+
+    fn eat_any(&mut self, operators: &[char]) -> Option<char> {
+        let trimmed = self.rest.trim_start();
+        let operator = trimmed.chars().next().filter(|ch| operators.contains(ch))?;
+        self.rest = &trimmed[operator.len_utf8()..];
+        Some(operator)
+    }
+}
+
+fn calc_add(
+    left: CalcValue,
+    right: CalcValue,
+    subtract: bool,
+) -> Result<CalcValue, StyleParseError> {
+    let sign = if subtract { -1.0 } else { 1.0 };
+    match (left, right) {
+        (CalcValue::Number(left), CalcValue::Number(right)) => {
+            Ok(CalcValue::Number(left + sign * right))
+        }
+        (CalcValue::Length(left), CalcValue::Length(right))
+            if std::mem::discriminant(&left) == std::mem::discriminant(&right) =>
+        {
+            let sum = val_number(left).unwrap_or(0.0) + sign * val_number(right).unwrap_or(0.0);
+            Ok(CalcValue::Length(with_val_number(left, sum)))
+        }
+        (CalcValue::Length(left), CalcValue::Length(right)) => Err(calc_error(format!(
+            "cannot combine {} with {}, Bevy's Val holds a single unit",
+            val_unit(left),
+            val_unit(right)
+        ))),
+        _ => Err(calc_error("cannot add a number to a length".to_string())),
+    }
+}
+
+fn calc_multiply(left: CalcValue, right: CalcValue) -> Result<CalcValue, StyleParseError> {
+    match (left, right) {
+        (CalcValue::Number(left), CalcValue::Number(right)) => Ok(CalcValue::Number(left * right)),
+        (CalcValue::Number(factor), CalcValue::Length(length))
+        | (CalcValue::Length(length), CalcValue::Number(factor)) => Ok(CalcValue::Length(
+            with_val_number(length, val_number(length).unwrap_or(0.0) * factor),
+        )),
+        (CalcValue::Length(_), CalcValue::Length(_)) => {
+            Err(calc_error("cannot multiply two lengths".to_string()))
+        }
+    }
+}
+
+fn calc_divide(left: CalcValue, right: CalcValue) -> Result<CalcValue, StyleParseError> {
+    let CalcValue::Number(divisor) = right else {
+        return Err(calc_error("can only divide by a number".to_string()));
+    };
+    if divisor == 0.0 {
+        return Err(calc_error("division by zero".to_string()));
+    }
+    calc_multiply(left, CalcValue::Number(divisor.recip()))
+}
+
+fn calc_error(reason: String) -> StyleParseError {
+    StyleParseError::InvalidCalc(reason)
+}
+
+fn val_unit(value: Val) -> &'static str {
+    match value {
+        Val::Px(_) => "px",
+        Val::Percent(_) => "%",
+        Val::Vw(_) => "vw",
+        Val::Vh(_) => "vh",
+        Val::VMin(_) => "vmin",
+        Val::VMax(_) => "vmax",
+        Val::Auto => "auto",
+    }
+}
+
+fn val_number(value: Val) -> Option<f32> {
+    match value {
+        Val::Px(number)
+        | Val::Percent(number)
+        | Val::Vw(number)
+        | Val::Vh(number)
+        | Val::VMin(number)
+        | Val::VMax(number) => Some(number),
+        Val::Auto => None,
+    }
+}
+
+fn with_val_number(unit: Val, number: f32) -> Val {
+    match unit {
+        Val::Px(_) => Val::Px(number),
+        Val::Percent(_) => Val::Percent(number),
+        Val::Vw(_) => Val::Vw(number),
+        Val::Vh(_) => Val::Vh(number),
+        Val::VMin(_) => Val::VMin(number),
+        Val::VMax(_) => Val::VMax(number),
+        Val::Auto => Val::Auto,
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    if lowered == "transparent" {
+        return Ok(Color::NONE);
+    }
+    if let Some(hex) = trimmed.strip_prefix('#') {
+        return parse_hex_color(hex)
+            .map(Color::from)
+            .ok_or_else(|| StyleParseError::InvalidColor(trimmed.to_string()));
+    }
+    if let Some(args) = lowered
+        .strip_prefix("rgba(")
+        .or_else(|| lowered.strip_prefix("rgb("))
+    {
+        return parse_rgb_function(args, trimmed);
+    }
+    if let Some(args) = lowered
+        .strip_prefix("hsla(")
+        .or_else(|| lowered.strip_prefix("hsl("))
+    {
+        return parse_hsl_function(args, trimmed);
+    }
+    let color = match lowered.as_str() {
+        "black" => basic::BLACK,
+        "silver" => basic::SILVER,
+        "gray" | "grey" => basic::GRAY,
+        "white" => basic::WHITE,
+        "maroon" => basic::MAROON,
+        "red" => basic::RED,
+        "purple" => basic::PURPLE,
+        "fuchsia" => basic::FUCHSIA,
+        "green" => basic::GREEN,
+        "lime" => basic::LIME,
+        "olive" => basic::OLIVE,
+        "yellow" => basic::YELLOW,
+        "navy" => basic::NAVY,
+        "blue" => basic::BLUE,
+        "teal" => basic::TEAL,
+        "aqua" => basic::AQUA,
+        _ => extended_color(&lowered)
+            .ok_or_else(|| StyleParseError::InvalidColor(trimmed.to_string()))?,
+    };
+    Ok(Color::from(color))
+}
+
+/// Parses the digits of a `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` color. Shorthand forms are
+/// expanded by doubling each digit, so `f0a` reads as `ff00aa`.
+fn parse_hex_color(hex: &str) -> Option<Srgba> {
+    if !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    match hex.len() {
+        3 | 4 => Srgba::hex(hex.chars().flat_map(|ch| [ch, ch]).collect::<String>()).ok(),
+        6 | 8 => Srgba::hex(hex).ok(),
+        _ => None,
+    }
+}
+
+/// Parses the arguments of `rgb()`/`rgba()` (everything after the opening parenthesis), in either
+/// the comma-separated or the space-separated `r g b / a` form.
+fn parse_rgb_function(args: &str, original: &str) -> Result<Color, StyleParseError> {
+    let invalid = || StyleParseError::InvalidColor(original.to_string());
+    let args = args.strip_suffix(')').ok_or_else(invalid)?;
+    let parts: SmallVec<[&str; 4]> = args
+        .split(|ch: char| ch == ',' || ch == '/' || ch.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let (channels, alpha) = match parts.as_slice() {
+        [r, g, b] => ([*r, *g, *b], None),
+        [r, g, b, a] => ([*r, *g, *b], Some(*a)),
+        _ => return Err(invalid()),
+    };
+    let mut rgb = [0.0; 3];
+    for (slot, channel) in rgb.iter_mut().zip(channels) {
+        *slot = match channel.strip_suffix('%') {
+            Some(percent) => parse_number(percent)? / 100.0,
+            None => parse_number(channel)? / 255.0,
+        }
+        .clamp(0.0, 1.0);
+    }
+    let alpha = match alpha {
+        Some(alpha) => parse_unit_float(alpha, 0.0, 1.0)?,
+        None => 1.0,
+    };
+    Ok(Color::srgba(rgb[0], rgb[1], rgb[2], alpha))
+}
+
+/// Parses the arguments of `hsl()`/`hsla()`, in the same two forms as [`parse_rgb_function`]. Hue
+/// is in degrees with an optional `deg` suffix and wraps around; saturation and lightness must be
+/// percentages.
+fn parse_hsl_function(args: &str, original: &str) -> Result<Color, StyleParseError> {
+    let invalid = || StyleParseError::InvalidColor(original.to_string());
+    let args = args.strip_suffix(')').ok_or_else(invalid)?;
+    let parts: SmallVec<[&str; 4]> = args
+        .split(|ch: char| ch == ',' || ch == '/' || ch.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let (hue, saturation, lightness, alpha) = match parts.as_slice() {
+        [h, s, l] => (*h, *s, *l, None),
+        [h, s, l, a] => (*h, *s, *l, Some(*a)),
+        _ => return Err(invalid()),
+    };
+    let hue = parse_number(hue.strip_suffix("deg").unwrap_or(hue))?.rem_euclid(360.0);
+    let mut percents = [0.0; 2];
+    for (slot, channel) in percents.iter_mut().zip([saturation, lightness]) {
+        let percent = channel.strip_suffix('%').ok_or_else(invalid)?;
+        *slot = (parse_number(percent)? / 100.0).clamp(0.0, 1.0);
+    }
+    let alpha = match alpha {
+        Some(alpha) => parse_unit_float(alpha, 0.0, 1.0)?,
+        None => 1.0,
+    };
+    Ok(Color::hsla(hue, percents[0], percents[1], alpha))
+}
+
+fn parse_display(value: &str) -> Result<Display, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "flex" => Ok(Display::Flex),
+        "grid" => Ok(Display::Grid),
+        "block" => Ok(Display::Block),
+        "none" => Ok(Display::None),
+        "inline-flex" => Ok(Display::Flex),
+        "inline-grid" => Ok(Display::Grid),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
+fn is_inline_display(value: &str) -> bool {
+    let trimmed = value.trim();
+    trimmed.eq_ignore_ascii_case("inline-flex") || trimmed.eq_ignore_ascii_case("inline-grid")
+}
+
+/// `flex-grow` and `flex-shrink` take a plain number, read through [`parse_unit_float`] like
+/// `opacity`. Unlike opacity, a percentage is no factor at all and a negative factor invalidates
+/// the declaration instead of clamping to zero.
+fn parse_flex_factor(value: &str) -> Result<f32, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.ends_with('%') {
+        return Err(StyleParseError::PercentNotAllowed);
+    }
+    if trimmed.starts_with('-') {
+        return Err(StyleParseError::InvalidNumber);
+    }
+    parse_unit_float(trimmed, 0.0, f32::MAX)
+}
+
+/// Width over height, written as a single number or as `width / height`; the ratio has to come
+/// out positive.
+fn parse_aspect_ratio(value: &str) -> Result<f32, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let ratio = match trimmed.split_once('/') {
+        Some((width, height)) => parse_number(width)? / parse_number(height)?,
+        None => parse_number(trimmed)?,
+    };
+    if !ratio.is_finite() || ratio <= 0.0 {
+        return Err(StyleParseError::InvalidNumber);
+    }
+    Ok(ratio)
+}
+
+fn parse_flex_direction(value: &str) -> Result<FlexDirection, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "row" => Ok(FlexDirection::Row),
+        "row-reverse" => Ok(FlexDirection::RowReverse),
+        "column" => Ok(FlexDirection::Column),
+        "column-reverse" => Ok(FlexDirection::ColumnReverse),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
+fn parse_flex_wrap(value: &str) -> Result<FlexWrap, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "nowrap" => Ok(FlexWrap::NoWrap),
+        "wrap" => Ok(FlexWrap::Wrap),
+        "wrap-reverse" => Ok(FlexWrap::WrapReverse),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
+fn parse_text_transform(value: &str) -> Result<TextTransform, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "none" => Ok(TextTransform::None),
+        "uppercase" => Ok(TextTransform::Uppercase),
+        "lowercase" => Ok(TextTransform::Lowercase),
+        "capitalize" => Ok(TextTransform::Capitalize),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
+/// One keyword for both axes, or `overflow-x` followed by `overflow-y`.
+fn parse_overflow(value: &str) -> Result<Overflow, StyleParseError> {
+    let axes = split_top_level_whitespace(value)
+        .into_iter()
+        .map(parse_overflow_axis)
+        .collect::<Result<SmallVec<[OverflowAxis; 2]>, _>>()?;
+    match axes.as_slice() {
+        [] => Err(StyleParseError::Empty),
+        [both] => Ok(Overflow { x: *both, y: *both }),
+        [x, y] => Ok(Overflow { x: *x, y: *y }),
+        _ => Err(StyleParseError::WrongArity {
+            expected: "1-2 values",
+            found: axes.len(),
+        }),
+    }
+}
+
+/// Bevy draws no scrollbars, so `auto` scrolls like `scroll` does.
+fn parse_overflow_axis(value: &str) -> Result<OverflowAxis, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "visible" => Ok(OverflowAxis::Visible),
+        "hidden" => Ok(OverflowAxis::Hidden),
+        "clip" => Ok(OverflowAxis::Clip),
+        "scroll" | "auto" => Ok(OverflowAxis::Scroll),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
+/// `fixed` has no viewport-relative counterpart in Bevy UI, so it is laid out like `absolute`.
+fn parse_position(value: &str) -> Result<PositionType, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "static" | "relative" => Ok(PositionType::Relative),
+        "absolute" | "fixed" => Ok(PositionType::Absolute),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
+fn parse_align_self(value: &str) -> Result<AlignSelf, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "auto" | "normal" => Ok(AlignSelf::Auto),
+        "start" => Ok(AlignSelf::Start),
+        "end" => Ok(AlignSelf::End),
+        "flex-start" => Ok(AlignSelf::FlexStart),
+        "flex-end" => Ok(AlignSelf::FlexEnd),
+        "center" => Ok(AlignSelf::Center),
+        "baseline" => Ok(AlignSelf::Baseline),
+        "stretch" => Ok(AlignSelf::Stretch),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
+fn parse_align_content(value: &str) -> Result<AlignContent, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "default" | "normal" => Ok(AlignContent::Default),
+        "start" => Ok(AlignContent::Start),
+        "end" => Ok(AlignContent::End),
+        "flex-start" => Ok(AlignContent::FlexStart),
+        "flex-end" => Ok(AlignContent::FlexEnd),
+        "center" => Ok(AlignContent::Center),
+        "stretch" => Ok(AlignContent::Stretch),
+        "space-between" => Ok(AlignContent::SpaceBetween),
+        "space-around" => Ok(AlignContent::SpaceAround),
+        "space-evenly" => Ok(AlignContent::SpaceEvenly),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
+fn parse_align_items(value: &str) -> Result<AlignItems, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
         "default" | "normal" | "auto" => Ok(AlignItems::Default),
         "start" => Ok(AlignItems::Start),
         "end" => Ok(AlignItems::End),
@@ -1258,6 +2776,59 @@ fn parse_align_items(value: &str) -> Result<AlignItems, StyleParseError> {
     }
 }
 
+/// Splits a comma-separated family list, unquoting quoted names and joining the words of unquoted
+/// ones with single spaces.
+fn parse_font_family(value: &str) -> Result<FontFamily, StyleParseError> {
+    let mut families = SmallVec::new();
+    for family in value.split(',') {
+        let family = family.trim();
+        let name = match family
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+            .or_else(|| {
+                family
+                    .strip_prefix('\'')
+                    .and_then(|rest| rest.strip_suffix('\''))
+            }) {
+            Some(quoted) => quoted.to_string(),
+            None => family
+                .split_ascii_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        if name.is_empty() {
+            return Err(StyleParseError::Empty);
+        }
+        families.push(name);
+    }
+    Ok(FontFamily(families))
+}
+
+fn parse_cursor(value: &str) -> Result<CursorStyle, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let lowered = trimmed.to_ascii_lowercase();
+    match lowered.as_str() {
+        "auto" => Ok(CursorStyle::Auto),
+        "default" => Ok(CursorStyle::Default),
+        "pointer" => Ok(CursorStyle::Pointer),
+        "text" => Ok(CursorStyle::Text),
+        "move" => Ok(CursorStyle::Move),
+        "grab" => Ok(CursorStyle::Grab),
+        "grabbing" => Ok(CursorStyle::Grabbing),
+        "wait" => Ok(CursorStyle::Wait),
+        "progress" => Ok(CursorStyle::Progress),
+        "help" => Ok(CursorStyle::Help),
+        "crosshair" => Ok(CursorStyle::Crosshair),
+        "not-allowed" => Ok(CursorStyle::NotAllowed),
+        "col-resize" => Ok(CursorStyle::ColResize),
+        "row-resize" => Ok(CursorStyle::RowResize),
+        _ => Err(StyleParseError::InvalidKeyword(trimmed.to_string())),
+    }
+}
+
 fn parse_justify_content(value: &str) -> Result<JustifyContent, StyleParseError> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -1285,6 +2856,23 @@ fn parse_number(raw: &str) -> Result<f32, StyleParseError> {
         .map_err(|_| StyleParseError::InvalidNumber)
 }
 
+/// Parses a bare number or a percentage (`50%` becomes `0.5`) and clamps it into `min..=max`, so
+/// numeric properties share one set of range rules.
+fn parse_unit_float(value: &str, min: f32, max: f32) -> Result<f32, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    let number = match trimmed.strip_suffix('%') {
+        Some(percent) => parse_number(percent)? / 100.0,
+        None => parse_number(trimmed)?,
+    };
+    if !number.is_finite() {
+        return Err(StyleParseError::InvalidNumber);
+    }
+    Ok(number.clamp(min, max))
+}
+
 fn split_unit(value: &str) -> (&str, &str) {
     let bytes = value.as_bytes();
     let mut split = value.len();
@@ -1299,10 +2887,10 @@ fn split_unit(value: &str) -> (&str, &str) {
     value.split_at(split)
 }
 
-fn parse_val_list(value: &str) -> Result<SmallVec<[Val; 4]>, StyleParseError> {
+fn parse_val_list(value: &str, root_font_px: f32) -> Result<SmallVec<[Val; 4]>, StyleParseError> {
     let mut values = SmallVec::new();
-    for token in value.split_whitespace() {
-        values.push(parse_val(token)?);
+    for token in split_top_level_whitespace(value) {
+        values.push(parse_val(token, root_font_px)?);
     }
     if values.is_empty() {
         return Err(StyleParseError::Empty);
@@ -1316,8 +2904,8 @@ fn parse_val_list(value: &str) -> Result<SmallVec<[Val; 4]>, StyleParseError> {
     Ok(values)
 }
 
-fn parse_ui_rect(value: &str) -> Result<UiRect, StyleParseError> {
-    let values = parse_val_list(value)?;
+fn parse_ui_rect(value: &str, root_font_px: f32) -> Result<UiRect, StyleParseError> {
+    let values = parse_val_list(value, root_font_px)?;
     let rect = match values.as_slice() {
         [all] => UiRect::all(*all),
         [vertical, horizontal] => UiRect::new(*horizontal, *horizontal, *vertical, *vertical),
@@ -1333,9 +2921,9 @@ fn parse_ui_rect(value: &str) -> Result<UiRect, StyleParseError> {
     Ok(rect)
 }
 
-fn parse_border_radius(value: &str) -> Result<BorderRadius, StyleParseError> {
+fn parse_border_radius(value: &str, root_font_px: f32) -> Result<BorderRadius, StyleParseError> {
     let value = value.split_once('/').map(|(left, _)| left).unwrap_or(value);
-    let values = parse_val_list(value)?;
+    let values = parse_val_list(value, root_font_px)?;
     let radius = match values.as_slice() {
         [all] => BorderRadius::all(*all),
         [first, second] => BorderRadius::new(*first, *second, *first, *second),
@@ -1351,8 +2939,8 @@ fn parse_border_radius(value: &str) -> Result<BorderRadius, StyleParseError> {
     Ok(radius)
 }
 
-fn parse_gap(value: &str) -> Result<(Val, Val), StyleParseError> {
-    let values = parse_val_list(value)?;
+fn parse_gap(value: &str, root_font_px: f32) -> Result<(Val, Val), StyleParseError> {
+    let values = parse_val_list(value, root_font_px)?;
     match values.as_slice() {
         [all] => Ok((*all, *all)),
         [row, column] => Ok((*row, *column)),
@@ -1363,17 +2951,215 @@ fn parse_gap(value: &str) -> Result<(Val, Val), StyleParseError> {
     }
 }
 
+struct BackgroundParse {
+    color: Color,
+    has_extras: bool,
+}
+
+/// Picks the color layer out of a `background` shorthand; images, positions and repeat keywords
+/// have no Bevy UI counterpart and only mark the result as having extras.
+fn parse_background_shorthand(value: &str) -> Result<BackgroundParse, StyleParseError> {
+    let mut color = None;
+    let mut has_extras = false;
+    for token in split_top_level_whitespace(value) {
+        match parse_color(token) {
+            Ok(parsed) if color.is_none() => color = Some(parsed),
+            _ => has_extras = true,
+        }
+    }
+    let Some(color) = color else {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(StyleParseError::Empty);
+        }
+        return Err(StyleParseError::InvalidColor(trimmed.to_string()));
+    };
+    Ok(BackgroundParse { color, has_extras })
+}
+
+struct TransitionParse {
+    spec: TransitionSpec,
+    has_extras: bool,
+}
+
+/// Parses the first comma-separated `transition`; `has_extras` flags any that follow it.
+fn parse_transition(value: &str) -> Result<TransitionParse, StyleParseError> {
+    let layers = split_top_level(value, |ch| ch == ',');
+    let Some(first) = layers.first() else {
+        return Err(StyleParseError::Empty);
+    };
+    Ok(TransitionParse {
+        spec: parse_single_transition(first)?,
+        has_extras: layers.len() > 1,
+    })
+}
+
+/// One `[property] [duration] [easing] [delay]` layer, in any order; the first time is the
+/// duration and the second the delay, as in CSS.
+fn parse_single_transition(layer: &str) -> Result<TransitionSpec, StyleParseError> {
+    let mut property = None;
+    let mut times = SmallVec::<[f32; 2]>::new();
+    let mut easing = None;
+    for token in split_top_level_whitespace(layer) {
+        if let Ok(time) = parse_time(token) {
+            if times.len() == 2 {
+                return Err(StyleParseError::InvalidKeyword(token.to_string()));
+            }
+            times.push(time);
+        } else if let Some(parsed) = parse_transition_easing(token) {
+            if easing.replace(parsed).is_some() {
+                return Err(StyleParseError::InvalidKeyword(token.to_string()));
+            }
+        } else if property.is_none()
+            && token
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        {
+            property = Some(token.to_ascii_lowercase());
+        } else {
+            return Err(StyleParseError::InvalidKeyword(token.to_string()));
+        }
+    }
+    let duration_secs = times.first().copied().unwrap_or(0.0);
+    if duration_secs < 0.0 {
+        return Err(StyleParseError::InvalidNumber);
+    }
+    Ok(TransitionSpec {
+        property: property.unwrap_or_else(|| "all".to_string()),
+        duration_secs,
+        delay_secs: times.get(1).copied().unwrap_or(0.0),
+        easing: easing.unwrap_or_default(),
+    })
+}
+
+/// A CSS `<time>` in seconds, from `s` or `ms`.
+fn parse_time(value: &str) -> Result<f32, StyleParseError> {
+    let trimmed = value.trim();
+    if let Some(number) = trimmed.strip_suffix("ms") {
+        return Ok(parse_number(number)? / 1000.0);
+    }
+    match trimmed.strip_suffix('s') {
+        Some(number) => parse_number(number),
+        None => Err(StyleParseError::InvalidNumber),
+    }
+}
+
+fn parse_transition_easing(value: &str) -> Option<TransitionEasing> {
+    match value.to_ascii_lowercase().as_str() {
+        "linear" => Some(TransitionEasing::Linear),
+        "ease" => Some(TransitionEasing::Ease),
+        "ease-in" => Some(TransitionEasing::EaseIn),
+        "ease-out" => Some(TransitionEasing::EaseOut),
+        "ease-in-out" => Some(TransitionEasing::EaseInOut),
+        "step-start" => Some(TransitionEasing::StepStart),
+        "step-end" => Some(TransitionEasing::StepEnd),
+        _ => None,
+    }
+}
+
+/// Splits on whitespace outside parentheses, so `url(a b.png)` or `rgb(0, 0, 0)` stay whole.
+fn split_top_level_whitespace(value: &str) -> Vec<&str> {
+    split_top_level(value, char::is_whitespace)
+}
+
+/// Splits `value` on separators that sit outside parentheses, skipping empty pieces.
+pub(crate) fn split_top_level(value: &str, is_separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+    for (index, ch) in value.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && is_separator(ch) => {
+                if let Some(token_start) = start.take() {
+                    let token = value[token_start..index].trim();
+                    if !token.is_empty() {
+                        tokens.push(token);
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(index);
+    }
+    if let Some(token_start) = start {
+        let token = value[token_start..].trim();
+        if !token.is_empty() {
+            tokens.push(token);
+        }
+    }
+    tokens
+}
+
+/// Parses `box-shadow` layers of 2-4 lengths (offset-x, offset-y, blur, spread) and an optional
+/// color, which defaults to black. `inset` shadows have no Bevy UI counterpart and are rejected.
+fn parse_box_shadow(value: &str, root_font_px: f32) -> Result<Vec<ShadowStyle>, StyleParseError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(StyleParseError::Empty);
+    }
+    if trimmed.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+    split_top_level(trimmed, |ch| ch == ',')
+        .into_iter()
+        .map(|layer| parse_shadow_layer(layer, root_font_px))
+        .collect()
+}
+
+fn parse_shadow_layer(layer: &str, root_font_px: f32) -> Result<ShadowStyle, StyleParseError> {
+    let mut lengths: SmallVec<[Val; 4]> = SmallVec::new();
+    let mut color = None;
+    for token in split_top_level_whitespace(layer) {
+        if token.eq_ignore_ascii_case("inset") {
+            return Err(StyleParseError::InvalidKeyword(token.to_string()));
+        }
+        match parse_length(token, root_font_px) {
+            Ok(Val::Auto) => return Err(StyleParseError::InvalidKeyword(token.to_string())),
+            Ok(val) => lengths.push(val),
+            Err(StyleParseError::InvalidNumber) if color.is_none() => {
+                color = Some(parse_color(token)?)
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    let [x_offset, y_offset, rest @ ..] = lengths.as_slice() else {
+        return Err(StyleParseError::WrongArity {
+            expected: "2-4 lengths",
+            found: lengths.len(),
+        });
+    };
+    if rest.len() > 2 {
+        return Err(StyleParseError::WrongArity {
+            expected: "2-4 lengths",
+            found: lengths.len(),
+        });
+    }
+    Ok(ShadowStyle {
+        color: color.unwrap_or(Color::BLACK),
+        x_offset: *x_offset,
+        y_offset: *y_offset,
+        blur_radius: rest.first().copied().unwrap_or(Val::ZERO),
+        spread_radius: rest.get(1).copied().unwrap_or(Val::ZERO),
+    })
+}
+
 struct BorderWidthParse {
     width: Val,
     has_extras: bool,
 }
 
-fn parse_border_width_shorthand(value: &str) -> Result<BorderWidthParse, StyleParseError> {
+fn parse_border_width_shorthand(
+    value: &str,
+    root_font_px: f32,
+) -> Result<BorderWidthParse, StyleParseError> {
     let mut width = None;
     let mut has_extras = false;
     let mut unsupported_unit = None;
-    for token in value.split_whitespace() {
-        match parse_val(token) {
+    for token in split_top_level_whitespace(value) {
+        match parse_length(token, root_font_px) {
             Ok(val) => {
                 if width.is_none() {
                     width = Some(val);
@@ -1385,6 +3171,9 @@ fn parse_border_width_shorthand(value: &str) -> Result<BorderWidthParse, StylePa
                 has_extras = true;
                 unsupported_unit = Some(unit);
             }
+            Err(StyleParseError::PercentNotAllowed) => {
+                return Err(StyleParseError::PercentNotAllowed);
+            }
             Err(_) => {
                 has_extras = true;
             }
@@ -1398,3 +3187,530 @@ fn parse_border_width_shorthand(value: &str) -> Result<BorderWidthParse, StylePa
     };
     Ok(BorderWidthParse { width, has_extras })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute<'a>(name: &'a str, value: Option<&'a str>) -> Attribute<Cow<'a, str>> {
+        build_attribute(
+            Cow::Borrowed(name),
+            value.map(Cow::Borrowed),
+            AttributeOptions::default(),
+        )
+    }
+
+    #[test]
+    fn draggable_keeps_its_three_states() {
+        assert_eq!(
+            attribute("draggable", Some("auto")),
+            Attribute::Draggable(Draggable::Auto)
+        );
+        assert_eq!(
+            attribute("draggable", Some("false")),
+            Attribute::Draggable(Draggable::False)
+        );
+        assert_eq!(
+            attribute("draggable", None),
+            Attribute::Draggable(Draggable::Auto)
+        );
+    }
+
+    #[test]
+    fn spellcheck_keeps_its_three_states() {
+        assert_eq!(
+            attribute("spellcheck", None),
+            Attribute::SpellCheck(SpellCheck::True)
+        );
+        assert_eq!(
+            attribute("spellcheck", Some("false")),
+            Attribute::SpellCheck(SpellCheck::False)
+        );
+        assert_eq!(
+            attribute("spellcheck", Some("maybe")),
+            Attribute::SpellCheck(SpellCheck::Default)
+        );
+    }
+
+    #[test]
+    fn boolean_attributes_serialize_by_presence() {
+        assert_eq!(
+            attribute("disabled", None).to_html().as_deref(),
+            Some("disabled")
+        );
+        assert_eq!(
+            attribute("disabled", Some("disabled")).to_html().as_deref(),
+            Some("disabled")
+        );
+        assert_eq!(attribute("disabled", Some("false")).to_html(), None);
+    }
+
+    #[test]
+    fn enumerated_attributes_round_trip_their_state() {
+        for (name, value) in [
+            ("spellcheck", "false"),
+            ("spellcheck", "true"),
+            ("contenteditable", "false"),
+            ("contenteditable", "true"),
+        ] {
+            let html = attribute(name, Some(value)).to_html().unwrap();
+            assert_eq!(html, format!("{name}=\"{value}\""));
+            let (_, reparsed) = html.split_once('=').unwrap();
+            assert_eq!(
+                attribute(name, Some(reparsed.trim_matches('"'))),
+                attribute(name, Some(value))
+            );
+        }
+    }
+
+    #[test]
+    fn removing_a_middle_attribute_keeps_the_index_consistent() {
+        let mut attributes = Attributes::<Cow<'_, str>>::default();
+        attributes.add_raw_attribute(Cow::Borrowed("id"), Some(Cow::Borrowed("main")));
+        attributes.add_raw_attribute(Cow::Borrowed("title"), Some(Cow::Borrowed("hint")));
+        attributes.add_raw_attribute(Cow::Borrowed("class"), Some(Cow::Borrowed("a")));
+
+        let title = std::mem::discriminant(&Attribute::Title(Cow::Borrowed("")));
+        assert!(attributes.remove(title).is_some());
+        assert_eq!(attributes.id(), Some("main"));
+        assert!(attributes.has_class("a"));
+
+        // The class entry moved down a slot, so replacing it must not touch `id`.
+        attributes.add_raw_attribute(Cow::Borrowed("class"), Some(Cow::Borrowed("b")));
+        assert_eq!(attributes.items.len(), 2);
+        assert_eq!(attributes.id(), Some("main"));
+        assert!(attributes.has_class("b"));
+        assert!(attributes.remove(title).is_none());
+    }
+
+    #[test]
+    fn each_bad_property_collects_one_warning() {
+        let mut cx = StyleContext::default();
+        let style = StyleAttribute::parse_with_warnings(
+            Cow::Borrowed("width: 10px; height: tall; display: sideways; margin: 4px"),
+            &mut cx,
+        );
+        let properties: Vec<_> = cx
+            .warnings
+            .iter()
+            .map(|warning| warning.property.as_str())
+            .collect();
+        assert_eq!(properties, ["height", "display"]);
+        assert_eq!(style.declarations.len(), 2);
+    }
+
+    fn declarations(raw: &str) -> SmallVec<[StyleDeclaration; 8]> {
+        StyleAttribute::parse(Cow::Borrowed(raw)).declarations
+    }
+
+    #[test]
+    fn unit_floats_clamp_out_of_range_values() {
+        assert_eq!(
+            declarations("opacity: 1.5")[..],
+            [StyleDeclaration::Opacity(1.0)]
+        );
+        assert_eq!(
+            declarations("opacity: -2")[..],
+            [StyleDeclaration::Opacity(0.0)]
+        );
+        assert_eq!(
+            declarations("opacity: 40%")[..],
+            [StyleDeclaration::Opacity(0.4)]
+        );
+        assert_eq!(
+            declarations("flex-grow: 2.5")[..],
+            [StyleDeclaration::FlexGrow(2.5)]
+        );
+    }
+
+    #[test]
+    fn unit_floats_reject_non_numeric_values() {
+        for raw in ["opacity: half", "flex-grow: lots", "flex-shrink: 50%"] {
+            let style = StyleAttribute::parse(Cow::Borrowed(raw));
+            assert!(style.declarations.is_empty(), "{raw}");
+            assert_eq!(style.unsupported.len(), 1, "{raw}");
+        }
+    }
+
+    #[test]
+    fn partial_opacity_is_reported_as_a_background_fade() {
+        let mut cx = StyleContext::default();
+        let style = StyleAttribute::parse_with_warnings(Cow::Borrowed("opacity: 0"), &mut cx);
+        assert_eq!(style.declarations[..], [StyleDeclaration::Opacity(0.0)]);
+        assert_eq!(
+            style.unsupported[..],
+            [UnsupportedStyle {
+                property: Cow::Borrowed("opacity"),
+                value: Cow::Borrowed("0"),
+            }]
+        );
+        assert_eq!(cx.warnings.len(), 1);
+
+        let mut cx = StyleContext::default();
+        let style = StyleAttribute::parse_with_warnings(Cow::Borrowed("opacity: 1"), &mut cx);
+        assert!(style.unsupported.is_empty());
+        assert!(cx.warnings.is_empty());
+    }
+
+    #[test]
+    fn background_shorthand_keeps_its_color() {
+        assert_eq!(
+            declarations("background: red")[..],
+            [StyleDeclaration::BackgroundColor(Color::from(basic::RED))]
+        );
+        let style = StyleAttribute::parse(Cow::Borrowed("background: url(x.png)"));
+        assert!(style.declarations.is_empty());
+        assert_eq!(style.unsupported.len(), 1);
+        assert_eq!(style.unsupported[0].property, "background");
+    }
+
+    #[test]
+    fn box_shadow_with_four_lengths_and_a_color() {
+        assert_eq!(
+            declarations("box-shadow: 0 2px 4px 1px rgba(0, 0, 0, 0.3)")[..],
+            [StyleDeclaration::BoxShadow(vec![ShadowStyle {
+                color: Color::srgba(0.0, 0.0, 0.0, 0.3),
+                x_offset: Val::Px(0.0),
+                y_offset: Val::Px(2.0),
+                blur_radius: Val::Px(4.0),
+                spread_radius: Val::Px(1.0),
+            }])]
+        );
+    }
+
+    #[test]
+    fn box_shadow_with_two_lengths_defaults_the_rest() {
+        assert_eq!(
+            declarations("box-shadow: 3px 5px")[..],
+            [StyleDeclaration::BoxShadow(vec![ShadowStyle {
+                color: Color::BLACK,
+                x_offset: Val::Px(3.0),
+                y_offset: Val::Px(5.0),
+                blur_radius: Val::ZERO,
+                spread_radius: Val::ZERO,
+            }])]
+        );
+    }
+
+    fn owned_style(raw: &str) -> StyleAttribute {
+        StyleAttribute::parse(Cow::Borrowed(raw)).into_owned()
+    }
+
+    #[test]
+    fn merge_lets_the_higher_style_override_per_property() {
+        let base = owned_style("width: 10px; color: red");
+        let merged = base.merge(&owned_style("color: blue"));
+        assert_eq!(
+            merged.declarations[..],
+            [
+                StyleDeclaration::Width(Val::Px(10.0)),
+                StyleDeclaration::TextColor(Color::from(basic::BLUE)),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_keeps_important_declarations_of_the_lower_style() {
+        let base = owned_style("color: red !important");
+        let merged = base.merge(&owned_style("color: blue; height: 5px"));
+        assert_eq!(
+            merged.declarations[..],
+            [
+                StyleDeclaration::TextColor(Color::from(basic::RED)),
+                StyleDeclaration::Height(Val::Px(5.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn comparison_functions_fold_same_unit_arguments() {
+        assert_eq!(
+            declarations("width: clamp(10px, 15px, 20px)")[..],
+            [StyleDeclaration::Width(Val::Px(15.0))]
+        );
+        let mixed = owned_style("width: min(10px, 50%)");
+        assert!(mixed.declarations.is_empty());
+        assert_eq!(mixed.unsupported[0].property, "width");
+    }
+
+    #[test]
+    fn strict_bool_rejects_values_the_lenient_mode_accepts() {
+        let strict = AttributeOptions {
+            strict_bool: true,
+            ..AttributeOptions::default()
+        };
+        let parse = |value, options| {
+            build_attribute(
+                Cow::Borrowed("disabled"),
+                Some(Cow::Borrowed(value)),
+                options,
+            )
+        };
+        assert_eq!(
+            parse("maybe", AttributeOptions::default()),
+            Attribute::Disabled(true)
+        );
+        assert_eq!(parse("maybe", strict), Attribute::Disabled(false));
+        assert_eq!(parse("disabled", strict), Attribute::Disabled(true));
+    }
+
+    #[test]
+    fn font_family_keeps_names_in_order() {
+        let families: SmallVec<[String; 2]> = ["My Font".to_string(), "monospace".to_string()]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            declarations("font-family: 'My Font', monospace")[..],
+            [StyleDeclaration::FontFamily(FontFamily(families))]
+        );
+    }
+
+    #[test]
+    fn percent_border_width_is_refused_with_a_warning() {
+        let mut cx = StyleContext::default();
+        let style = StyleAttribute::parse_with_warnings(
+            Cow::Borrowed("border-width: 10%; width: 10%"),
+            &mut cx,
+        );
+        assert_eq!(
+            style.declarations[..],
+            [StyleDeclaration::Width(Val::Percent(10.0))]
+        );
+        assert_eq!(style.unsupported[0].property, "border-width");
+        assert_eq!(cx.warnings.len(), 1);
+        assert_eq!(cx.warnings[0].property, "border-width");
+    }
+
+    #[test]
+    fn font_size_parses_px_and_rem_and_rejects_keywords() {
+        assert_eq!(
+            declarations("font-size: 24px; font-size: 2rem")[..],
+            [
+                StyleDeclaration::FontSize(Val::Px(24.0)),
+                StyleDeclaration::FontSize(Val::Px(2.0 * BASE_FONT_PX)),
+            ]
+        );
+        let style = owned_style("font-size: huge");
+        assert!(style.declarations.is_empty());
+        assert_eq!(style.unsupported[0].property, "font-size");
+    }
+
+    #[test]
+    fn class_list_getter_reads_every_class() {
+        let mut attributes = Attributes::<Cow<'_, str>>::default();
+        attributes.add_raw_attribute(Cow::Borrowed("class"), Some(Cow::Borrowed(" card  wide ")));
+        let classes = attributes.class_list().unwrap();
+        assert_eq!(classes.iter().collect::<Vec<_>>(), ["card", "wide"]);
+        assert!(classes.contains("wide"));
+        assert!(Attributes::<Cow<'_, str>>::default().class_list().is_none());
+    }
+
+    #[test]
+    fn flex_factors_reject_negative_numbers() {
+        assert_eq!(
+            declarations("flex-grow: 2; flex-shrink: 0.5")[..],
+            [
+                StyleDeclaration::FlexGrow(2.0),
+                StyleDeclaration::FlexShrink(0.5),
+            ]
+        );
+        let style = owned_style("flex-grow: -1; flex-shrink: -2");
+        assert!(style.declarations.is_empty());
+        let properties: Vec<_> = style
+            .unsupported
+            .iter()
+            .map(|entry| entry.property.as_str())
+            .collect();
+        assert_eq!(properties, ["flex-grow", "flex-shrink"]);
+    }
+
+    #[test]
+    fn flex_wrap_reads_wrap_reverse() {
+        assert_eq!(
+            declarations("flex-wrap: wrap-reverse")[..],
+            [StyleDeclaration::FlexWrap(FlexWrap::WrapReverse)]
+        );
+        assert_eq!(owned_style("flex-wrap: sometimes").unsupported.len(), 1);
+    }
+
+    #[test]
+    fn align_self_reads_keywords_and_rejects_unknown_ones() {
+        assert_eq!(
+            declarations("align-self: flex-end; align-self: auto")[..],
+            [
+                StyleDeclaration::AlignSelf(AlignSelf::FlexEnd),
+                StyleDeclaration::AlignSelf(AlignSelf::Auto),
+            ]
+        );
+        assert_eq!(owned_style("align-self: sideways").unsupported.len(), 1);
+    }
+
+    #[test]
+    fn align_content_normal_is_the_default() {
+        assert_eq!(
+            declarations("align-content: normal; align-content: space-evenly")[..],
+            [
+                StyleDeclaration::AlignContent(AlignContent::Default),
+                StyleDeclaration::AlignContent(AlignContent::SpaceEvenly),
+            ]
+        );
+    }
+
+    #[test]
+    fn transition_parses_a_single_property_and_duration() {
+        assert_eq!(
+            declarations("transition: opacity 0.3s")[..],
+            [StyleDeclaration::Transition(TransitionSpec {
+                property: "opacity".to_string(),
+                duration_secs: 0.3,
+                delay_secs: 0.0,
+                easing: TransitionEasing::Ease,
+            })]
+        );
+    }
+
+    #[test]
+    fn inline_flex_lays_out_as_flex_and_records_the_keyword() {
+        let mut cx = StyleContext::default();
+        let style =
+            StyleAttribute::parse_with_warnings(Cow::Borrowed("display: inline-flex"), &mut cx);
+        assert_eq!(
+            style.declarations[..],
+            [StyleDeclaration::Display(Display::Flex)]
+        );
+        assert_eq!(
+            style.unsupported[..],
+            [UnsupportedStyle {
+                property: Cow::Borrowed("display"),
+                value: Cow::Borrowed("inline-flex"),
+            }]
+        );
+        assert_eq!(cx.warnings.len(), 1);
+    }
+
+    #[test]
+    fn hsl_colors_parse_with_optional_deg_and_alpha() {
+        assert_eq!(
+            parse_color("hsl(120deg, 100%, 50%)").ok(),
+            Some(Color::hsla(120.0, 1.0, 0.5, 1.0))
+        );
+        assert_eq!(
+            parse_color("hsla(480, 50%, 25%, 0.5)").ok(),
+            Some(Color::hsla(120.0, 0.5, 0.25, 0.5))
+        );
+        assert!(matches!(
+            parse_color("hsl(120, 100, 50%)"),
+            Err(StyleParseError::InvalidColor(_))
+        ));
+        assert!(matches!(
+            parse_color("hsl(120, 100%)"),
+            Err(StyleParseError::InvalidColor(_))
+        ));
+    }
+
+    #[test]
+    fn short_hex_colors_expand_each_digit() {
+        assert_eq!(parse_color("#fff").ok(), Some(Color::from(basic::WHITE)));
+        assert_eq!(
+            parse_color("#0f08").ok(),
+            Some(Color::from(Srgba::hex("00ff0088").unwrap()))
+        );
+        assert!(matches!(
+            parse_color("#ff"),
+            Err(StyleParseError::InvalidColor(_))
+        ));
+    }
+
+    #[test]
+    fn named_colors_keep_basic_keywords_and_aliases() {
+        assert_eq!(parse_color("grey").ok(), parse_color("gray").ok());
+        assert_eq!(parse_color("Red").ok(), Some(Color::from(basic::RED)));
+        assert_eq!(parse_color("transparent").ok(), Some(Color::NONE));
+        assert!(parse_color("TOMATO").is_ok());
+    }
+
+    #[test]
+    fn rem_resolves_against_the_context_root_font_size() {
+        assert_eq!(
+            declarations("width: 2rem")[..],
+            [StyleDeclaration::Width(Val::Px(32.0))]
+        );
+        let mut cx = StyleContext::new(10.0);
+        let style = StyleAttribute::parse_with_warnings(Cow::Borrowed("width: 2rem"), &mut cx);
+        assert_eq!(
+            style.declarations[..],
+            [StyleDeclaration::Width(Val::Px(20.0))]
+        );
+    }
+
+    #[test]
+    fn unknown_units_are_reported_as_unsupported() {
+        assert!(matches!(
+            parse_val("3furlongs", BASE_FONT_PX),
+            Err(StyleParseError::UnsupportedUnit(_))
+        ));
+        let mut cx = StyleContext::default();
+        let style = StyleAttribute::parse_with_warnings(Cow::Borrowed("width: 3furlongs"), &mut cx);
+        assert!(style.declarations.is_empty());
+        assert_eq!(style.unsupported[0].property, "width");
+        assert_eq!(cx.warnings[0].property, "width");
+    }
+
+    #[test]
+    fn calc_reduces_same_unit_expressions() {
+        assert_eq!(
+            declarations("width: calc(10px + 2 * 5px)")[..],
+            [StyleDeclaration::Width(Val::Px(20.0))]
+        );
+        assert_eq!(
+            declarations("width: calc((100% - 20%) / (1 + 1)); height: calc(2 * calc(1px + 2px))")
+                [..],
+            [
+                StyleDeclaration::Width(Val::Percent(40.0)),
+                StyleDeclaration::Height(Val::Px(6.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn calc_refuses_mixed_units_and_non_ascii_input() {
+        assert!(matches!(
+            parse_val("calc(100% - 20px)", BASE_FONT_PX),
+            Err(StyleParseError::InvalidCalc(_))
+        ));
+        let style = owned_style("width: calc(1px + ééé)");
+        assert!(style.declarations.is_empty());
+        assert_eq!(style.unsupported[0].property, "width");
+    }
+
+    #[test]
+    fn calc_refuses_deep_nesting_instead_of_overflowing() {
+        let nested = |depth: usize, open: &str| {
+            format!("calc({}1px{})", open.repeat(depth), ")".repeat(depth))
+        };
+        assert!(matches!(
+            parse_val(&nested(20, "("), BASE_FONT_PX),
+            Ok(Val::Px(1.0))
+        ));
+        for open in ["(", "calc(", "-("] {
+            assert!(matches!(
+                parse_val(&nested(5000, open), BASE_FONT_PX),
+                Err(StyleParseError::InvalidCalc(reason)) if reason == "nested too deeply"
+            ));
+        }
+    }
+
+    #[test]
+    fn aspect_ratio_reads_numbers_and_ratios_and_rejects_zero() {
+        assert_eq!(
+            declarations("aspect-ratio: 1; aspect-ratio: 16 / 9")[..],
+            [
+                StyleDeclaration::AspectRatio(1.0),
+                StyleDeclaration::AspectRatio(16.0 / 9.0),
+            ]
+        );
+        let style = owned_style("aspect-ratio: 0; aspect-ratio: -2");
+        assert!(style.declarations.is_empty());
+        assert_eq!(style.unsupported.len(), 2);
+    }
+}
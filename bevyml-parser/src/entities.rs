@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+
+/// Escapes `&`, `<` and `>` so text content re-serializes as valid markup.
+pub fn escape_text(text: &str) -> Cow<'_, str> {
+    escape(text, |ch| match ch {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        _ => None,
+    })
+}
+
+/// Escapes `&`, `<`, `>` and `"` for use inside a double-quoted attribute value.
+pub fn escape_attribute(value: &str) -> Cow<'_, str> {
+    escape(value, |ch| match ch {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '"' => Some("&quot;"),
+        _ => None,
+    })
+}
+
+fn escape(input: &str, replacement: impl Fn(char) -> Option<&'static str>) -> Cow<'_, str> {
+    if !input.chars().any(|ch| replacement(ch).is_some()) {
+        return Cow::Borrowed(input);
+    }
+    let mut escaped = String::with_capacity(input.len() + 8);
+    for ch in input.chars() {
+        match replacement(ch) {
+            Some(entity) => escaped.push_str(entity),
+            None => escaped.push(ch),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Decodes character references: numeric ones (`&#60;`, `&#x3C;`) and the named entities markup
+/// commonly relies on. Anything unrecognized is kept verbatim, like a browser would.
+pub fn decode_entities(input: &str) -> Cow<'_, str> {
+    if !input.contains('&') {
+        return Cow::Borrowed(input);
+    }
+    let mut decoded = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match decode_reference(rest) {
+            Some((ch, consumed)) => {
+                decoded.push(ch);
+                rest = &rest[consumed..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
+
+/// Decodes the reference at the start of `input` (which begins with `&`), returning the character
+/// and the number of bytes it spans including the closing `;`.
+fn decode_reference(input: &str) -> Option<(char, usize)> {
+    let end = input[1..].find(';')? + 1;
+    let body = &input[1..end];
+    let ch = if let Some(number) = body.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        char::from_u32(code)?
+    } else {
+        match body {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{a0}',
+            "copy" => '\u{a9}',
+            "reg" => '\u{ae}',
+            "trade" => '\u{2122}',
+            "hellip" => '\u{2026}',
+            "mdash" => '\u{2014}',
+            "ndash" => '\u{2013}',
+            "lsquo" => '\u{2018}',
+            "rsquo" => '\u{2019}',
+            "ldquo" => '\u{201c}',
+            "rdquo" => '\u{201d}',
+            "bull" => '\u{2022}',
+            "middot" => '\u{b7}',
+            "times" => '\u{d7}',
+            "divide" => '\u{f7}',
+            "euro" => '\u{20ac}',
+            _ => return None,
+        }
+    };
+    Some((ch, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping_round_trips_through_the_decoder() {
+        let text = "1 < 2 && 3 > 2";
+        assert_eq!(escape_text(text), "1 &lt; 2 &amp;&amp; 3 &gt; 2");
+        assert_eq!(decode_entities(&escape_text(text)), text);
+
+        let value = r#"say "hi" & <wave>"#;
+        assert_eq!(decode_entities(&escape_attribute(value)), value);
+        assert!(!escape_attribute(value).contains('"'));
+    }
+}
@@ -1,16 +1,37 @@
-use std::{borrow::Cow, fmt, ops::Range, str::FromStr};
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Range,
+    str::FromStr,
+};
 
+use bevy_color::{palettes::basic, Alpha, Color};
 use bevy_ecs::{bundle::Bundle, component::Component, name::Name};
+use bevy_log::warn;
 use bevy_reflect::Reflect;
-use bevy_ui::{widget::Text, BackgroundColor, BorderColor, Display, Node, UiRect, Val};
+use bevy_ui::{
+    widget::Text, AlignItems, BackgroundColor, BorderColor, BorderRadius, BoxShadow, Display,
+    FlexDirection, GlobalZIndex, Node, UiRect, Val,
+};
+use fnv::FnvHasher;
 use strum_macros::{AsRefStr, EnumString};
 
-use crate::attributes::{Attribute, Attributes, StyleDeclaration};
+use crate::{
+    attributes::{
+        Attribute, Attributes, CursorStyle, FontFamily, Hidden, StyleAttribute, StyleDeclaration,
+        TextTransform, TransitionSpec,
+    },
+    entities::escape_text,
+};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Component)]
 pub struct NodeId(pub(crate) u32);
 
 impl NodeId {
+    /// Id carried by bundles built in code rather than parsed from an [`ITree`](crate::itree::ITree).
+    pub const DETACHED: NodeId = NodeId(u32::MAX);
+
     pub(crate) fn new(index: usize) -> Self {
         let index = u32::try_from(index).expect("node index overflow");
         Self(index)
@@ -21,6 +42,153 @@ impl NodeId {
     }
 }
 
+/// Marks an element inside an `inert` subtree; such elements never receive interaction state.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub struct Inert;
+
+/// Marks content under `translate="no"` that localization tooling should leave untouched.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub struct NoTranslate;
+
+/// Marks content under `hidden="until-found"`: hidden from view but kept in layout and text
+/// extraction so in-page search can find and reveal it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub struct HiddenUntilFound;
+
+/// Path of the image an `<img>` displays, chosen from its `<picture>` sources when it has any.
+/// Loaded into an `ImageNode` once spawned.
+#[derive(Clone, Debug, PartialEq, Eq, Component, Reflect)]
+pub struct ImageSource(pub String);
+
+/// A piece of text from [`BevyNodeTree::text_runs`], flagged with whether it may be translated and
+/// carrying the text styling it resolved to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextRun {
+    pub text: String,
+    pub translate: bool,
+    /// `false` when the run sits in a subtree spawned with `Display::None` or hidden until found.
+    pub visible: bool,
+    pub color: Option<Color>,
+    pub font_size: Option<f32>,
+    pub node_id: NodeId,
+}
+
+/// One text node of an element with [`text_sections`](BevyNodeTree::text_sections), spawned as a
+/// `TextSpan` of the element's `Text` with the styling it resolved to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSection {
+    pub text: String,
+    pub bold: bool,
+    pub color: Option<Color>,
+    pub font_size: Option<f32>,
+    pub node_id: NodeId,
+}
+
+/// Marks the element carrying `autofocus`; the plugin focuses it once it is spawned.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub struct Autofocus;
+
+/// State of an `<input type="checkbox">` or `<input type="radio">`, seeded from its `checked`
+/// attribute; apps render the indicator and flip `checked` as the user toggles it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub struct ToggleControl {
+    pub checked: bool,
+    pub radio: bool,
+}
+
+/// State of a `<details>` element, seeded from its `open` attribute; flipping `open` shows or
+/// hides everything but the summary.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component, Reflect)]
+pub struct Disclosure {
+    pub open: bool,
+}
+
+/// Marks the content of a `<details>` other than its summary, holding the display it takes
+/// while the disclosure is open.
+#[derive(Copy, Clone, Debug, PartialEq, Component, Reflect)]
+pub struct DisclosureBody(pub Display);
+
+/// Columns and rows a `<td>` or `<th>` spans, from `colspan` and `rowspan`. Columns widen the cell
+/// in the flexbox table approximation; rows are left to the app.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Component, Reflect)]
+pub struct CellSpan {
+    pub col: u32,
+    pub row: u32,
+}
+
+impl Default for CellSpan {
+    fn default() -> Self {
+        Self { col: 1, row: 1 }
+    }
+}
+
+/// Reading of a `<progress>` or `<meter>`, with `value` clamped to `min..=max`; apps draw the bar
+/// from [`ProgressState::fraction`].
+#[derive(Copy, Clone, Debug, PartialEq, Component, Reflect)]
+pub struct ProgressState {
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ProgressState {
+    /// How far `value` is from `min` to `max`, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f32 {
+        (self.value - self.min) / (self.max - self.min)
+    }
+}
+
+/// Colors an element takes while in some state, from `<style>` rules ending in a pseudo-class;
+/// `None` keeps the color it has otherwise.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Reflect)]
+pub struct StateColors {
+    pub background_color: Option<Color>,
+    /// Applied to the element and the text nodes directly inside it.
+    pub text_color: Option<Color>,
+}
+
+impl StateColors {
+    /// The last `background-color` and `color` of `style`, `None` when it declares neither.
+    pub fn from_style(style: &StyleAttribute) -> Option<Self> {
+        let mut colors = StateColors::default();
+        for declaration in &style.declarations {
+            match declaration {
+                StyleDeclaration::BackgroundColor(color) => colors.background_color = Some(*color),
+                StyleDeclaration::TextColor(color) => colors.text_color = Some(*color),
+                _ => {}
+            }
+        }
+        (colors != StateColors::default()).then_some(colors)
+    }
+}
+
+/// Colors for each [`ColorScheme`](crate::stylesheet::ColorScheme), from `<style>` rules with
+/// those inside `@media (prefers-color-scheme: ...)` included. State styles win over them.
+#[derive(Copy, Clone, Debug, PartialEq, Component, Reflect)]
+pub struct ColorSchemeStyle {
+    pub light: StateColors,
+    pub dark: StateColors,
+}
+
+/// Colors from `:hover` rules, shown while the element's `Interaction` is hovered or pressed.
+#[derive(Copy, Clone, Debug, PartialEq, Component, Reflect)]
+pub struct HoverStyle(pub StateColors);
+
+/// Colors from `:checked` rules, shown while the element has Bevy's `Checked` component, which
+/// follows [`ToggleControl::checked`].
+#[derive(Copy, Clone, Debug, PartialEq, Component, Reflect)]
+pub struct CheckedStyle(pub StateColors);
+
+/// Colors from `:disabled` rules, shown while the element has Bevy's `InteractionDisabled`
+/// component. They win over the colors of every other state.
+#[derive(Copy, Clone, Debug, PartialEq, Component, Reflect)]
+pub struct DisabledStyle(pub StateColors);
+
+/// Line limit requested through `line-clamp` for a text node; Bevy does not truncate text on its
+/// own, so enforcing it is left to the app.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Component, Reflect)]
+pub struct MaxLines(pub u32);
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct TextPosition {
     pub column: usize,
@@ -31,6 +199,57 @@ impl TextPosition {
     pub const fn new(column: usize, row: usize) -> Self {
         Self { column, row }
     }
+
+    /// Zero-based row and byte column of `byte` in `source`, matching the positions tree-sitter
+    /// reports. Offsets past the end resolve to the end of the source.
+    pub fn of_byte(source: &str, byte: usize) -> Self {
+        let before = &source.as_bytes()[..byte.min(source.len())];
+        match before.iter().rposition(|&b| b == b'\n') {
+            Some(newline) => Self::new(
+                before.len() - newline - 1,
+                before.iter().filter(|&&b| b == b'\n').count(),
+            ),
+            None => Self::new(before.len(), 0),
+        }
+    }
+}
+
+/// Resolved text direction, inherited from the closest ancestor carrying a `dir` attribute.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum Direction {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// Parses an explicit `dir` value; anything other than `ltr`/`rtl` defers to the parent.
+    /// `auto` is resolved from the element's text instead, see [`Direction::of_strong_char`].
+    pub fn from_dir_attribute(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ltr" => Some(Direction::Ltr),
+            "rtl" => Some(Direction::Rtl),
+            _ => None,
+        }
+    }
+
+    /// The direction a strongly directional character sets, `None` for neutral and weak ones like
+    /// digits, punctuation and spaces. Approximates the Unicode bidi classes by treating letters
+    /// from the right-to-left script blocks as RTL and every other letter as LTR.
+    pub fn of_strong_char(c: char) -> Option<Self> {
+        if !c.is_alphabetic() {
+            return None;
+        }
+        let rtl = matches!(
+            c,
+            '\u{0590}'..='\u{08FF}'
+                | '\u{FB1D}'..='\u{FDFF}'
+                | '\u{FE70}'..='\u{FEFF}'
+                | '\u{10800}'..='\u{10FFF}'
+                | '\u{1E800}'..='\u{1EFFF}'
+        );
+        Some(if rtl { Direction::Rtl } else { Direction::Ltr })
+    }
 }
 
 /// Intermediary Node
@@ -48,12 +267,70 @@ pub struct INode<'source> {
     pub is_self_closing: bool,
     pub parent: Option<NodeId>,
     pub children: Range<usize>,
+    /// Number of an `<li>` inside an `<ol>`, honoring the list's `start` and the item's `value`.
+    pub list_ordinal: Option<i64>,
+    pub direction: Direction,
+    /// Set when this node or one of its ancestors carries the `inert` attribute.
+    pub inert: bool,
+    /// Set when the closest `translate` attribute on this node or its ancestors says `no`.
+    pub no_translate: bool,
+    /// The tag as written when it was an alias resolved to `node_type`, e.g. `center`.
+    pub tag_alias: Option<String>,
+    /// The rules of `<style>` elements matching this element, cascaded with its inline style
+    /// on top. `None` when no rule matches, and the inline style applies as written.
+    pub cascaded_style: Option<StyleAttribute>,
+    /// The `:hover` rules matching this element, cascaded like `cascaded_style`.
+    pub hover_style: Option<StyleAttribute>,
+    /// The `:checked` rules matching this element, cascaded like `cascaded_style`.
+    pub checked_style: Option<StyleAttribute>,
+    /// The `:disabled` rules matching this element, cascaded like `cascaded_style`.
+    pub disabled_style: Option<StyleAttribute>,
+    /// The rules matching this element in the light color scheme, `@media` ones included and
+    /// cascaded like `cascaded_style`; `None` unless an `@media (prefers-color-scheme: ...)` rule
+    /// matches it.
+    pub light_style: Option<StyleAttribute>,
+    /// Like `light_style`, for the dark color scheme.
+    pub dark_style: Option<StyleAttribute>,
 }
 
 #[derive(Debug, Clone)]
 pub struct BevyNodeTree {
     pub node: INodeBundle,
     pub text: Option<Text>,
+    pub inert: bool,
+    pub no_translate: bool,
+    pub autofocus: bool,
+    pub max_lines: Option<MaxLines>,
+    pub cursor: Option<CursorStyle>,
+    pub box_shadow: Option<BoxShadow>,
+    pub toggle: Option<ToggleControl>,
+    pub global_z_index: Option<GlobalZIndex>,
+    pub hidden_until_found: bool,
+    /// Computed font size in pixels, set on text-bearing nodes when they or an ancestor declare a
+    /// `font-size`.
+    pub font_size: Option<f32>,
+    pub image_source: Option<ImageSource>,
+    pub disclosure: Option<Disclosure>,
+    pub disclosure_body: Option<DisclosureBody>,
+    pub progress: Option<ProgressState>,
+    pub cell_span: Option<CellSpan>,
+    /// Families from the closest `font-family` on this text-bearing node or its ancestors.
+    pub font_family: Option<FontFamily>,
+    /// Color from the closest `color` on this text-bearing node or its ancestors.
+    pub text_color: Option<Color>,
+    pub transition: Option<TransitionSpec>,
+    pub hover_style: Option<HoverStyle>,
+    pub checked_style: Option<CheckedStyle>,
+    pub disabled_style: Option<DisabledStyle>,
+    pub color_scheme_style: Option<ColorSchemeStyle>,
+    /// Set on interactive elements carrying `disabled`.
+    pub disabled: bool,
+    /// Set on text-bearing nodes inside `<strong>` or `<b>`.
+    pub bold: bool,
+    /// Filled on elements holding only text and inline elements like `<strong>`, at least one of
+    /// the latter; they spawn as a single `Text` split into these sections instead of a node per
+    /// child.
+    pub text_sections: Vec<TextSection>,
     pub children: Vec<BevyNodeTree>,
 }
 
@@ -68,6 +345,26 @@ pub struct INodeBundle {
     pub attributes: Attributes,
 }
 
+impl INodeBundle {
+    /// Builds a bundle for code-driven UI, named after the element's tag and with no attributes.
+    pub fn new(node_type: NodeType, node: Node) -> Self {
+        Self {
+            id: NodeId::DETACHED,
+            name: Name::new(node_type.tag_name().into_owned()),
+            node,
+            background_color: BackgroundColor::DEFAULT,
+            border_color: BorderColor::DEFAULT,
+            node_kind: NodeKind { kind: node_type },
+            attributes: Attributes::default(),
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<Name>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
 impl fmt::Debug for INodeBundle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("INodeBundle")
@@ -77,12 +374,424 @@ impl fmt::Debug for INodeBundle {
     }
 }
 
+/// How [`BevyNodeTree::to_html_with`] writes a custom element that has no children.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyCustomElement {
+    /// `<my-el></my-el>`, which every HTML parser reads back the same way.
+    #[default]
+    ExplicitClose,
+    /// `<my-el/>`, the shorter form the bevyml grammar also accepts.
+    SelfClose,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HtmlOptions {
+    pub empty_custom_element: EmptyCustomElement,
+}
+
+impl BevyNodeTree {
+    /// Re-emits the tree as markup; void elements self-close and attributes are serialized through
+    /// [`Attribute::to_html`].
+    pub fn to_html(&self) -> String {
+        self.to_html_with(&HtmlOptions::default())
+    }
+
+    /// Like [`BevyNodeTree::to_html`], with control over the ambiguous serialization choices.
+    pub fn to_html_with(&self, options: &HtmlOptions) -> String {
+        let mut html = String::new();
+        self.write_html(&mut html, options);
+        html
+    }
+
+    /// The text of the subtree in document order, each run flagged so localization tooling can
+    /// skip `translate="no"` regions. Hidden text is included too, see [`TextRun::visible`].
+    pub fn text_runs(&self) -> Vec<TextRun> {
+        let mut runs = Vec::new();
+        self.collect_text_runs(true, &mut runs);
+        runs
+    }
+
+    fn collect_text_runs(&self, parent_visible: bool, runs: &mut Vec<TextRun>) {
+        let visible =
+            parent_visible && self.node.node.display != Display::None && !self.hidden_until_found;
+        if let Some(text) = &self.text {
+            runs.push(TextRun {
+                text: text.0.clone(),
+                translate: !self.no_translate,
+                visible,
+                color: self.text_color,
+                font_size: self.font_size,
+                node_id: self.node.id,
+            });
+        }
+        for child in &self.children {
+            child.collect_text_runs(visible, runs);
+        }
+    }
+
+    /// Depth-first search for the element whose `id` attribute equals `id`, this node included.
+    pub fn find_by_id(&self, id: &str) -> Option<&BevyNodeTree> {
+        if self.node.attributes.id() == Some(id) {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find_by_id(id))
+    }
+
+    pub fn find_by_id_mut(&mut self, id: &str) -> Option<&mut BevyNodeTree> {
+        if self.node.attributes.id() == Some(id) {
+            return Some(self);
+        }
+        self.children
+            .iter_mut()
+            .find_map(|child| child.find_by_id_mut(id))
+    }
+
+    /// Stable, indented dump of the subtree for snapshot tests: one line per node with its tag and
+    /// alphabetically sorted attributes, text nodes quoted.
+    pub fn canonical_debug(&self) -> String {
+        let mut out = String::new();
+        self.write_canonical(&mut out, 0);
+        out
+    }
+
+    fn write_canonical(&self, out: &mut String, depth: usize) {
+        for _ in 0..depth {
+            out.push_str("  ");
+        }
+        let kind = &self.node.node_kind.kind;
+        if *kind == NodeType::Text {
+            let text = self.text.as_ref().map_or("", |text| text.0.as_str());
+            out.push_str(&format!("{:?}\n", text));
+            return;
+        }
+        out.push_str(&kind.tag_name());
+        let mut attributes: Vec<String> = self
+            .node
+            .attributes
+            .items
+            .iter()
+            .filter_map(|attribute| attribute.to_html())
+            .collect();
+        attributes.sort();
+        for attribute in attributes {
+            out.push(' ');
+            out.push_str(&attribute);
+        }
+        out.push('\n');
+        for child in &self.children {
+            child.write_canonical(out, depth + 1);
+        }
+    }
+
+    /// Hashes the node kinds, attributes, text and children of this subtree with a fixed-key hasher,
+    /// so equal subtrees hash equal across runs and unchanged ones can skip respawning.
+    pub fn structural_hash(&self) -> u64 {
+        let mut hasher = FnvHasher::default();
+        self.hash_structure(&mut hasher);
+        hasher.finish()
+    }
+
+    fn hash_structure(&self, hasher: &mut FnvHasher) {
+        self.node.node_kind.kind.tag_name().hash(hasher);
+        self.text.as_ref().map(|text| text.0.as_str()).hash(hasher);
+        for attribute in &self.node.attributes.items {
+            attribute.to_html().hash(hasher);
+        }
+        // Inherited state changes what gets spawned even when the markup itself is identical.
+        self.inert.hash(hasher);
+        self.no_translate.hash(hasher);
+        self.autofocus.hash(hasher);
+        self.max_lines.map(|max_lines| max_lines.0).hash(hasher);
+        self.bold.hash(hasher);
+        self.children.len().hash(hasher);
+        for child in &self.children {
+            child.hash_structure(hasher);
+        }
+    }
+
+    fn write_html(&self, html: &mut String, options: &HtmlOptions) {
+        let kind = &self.node.node_kind.kind;
+        if *kind == NodeType::Text {
+            if let Some(text) = &self.text {
+                html.push_str(&escape_text(&text.0));
+            }
+            return;
+        }
+
+        let tag_name = kind.tag_name();
+        html.push('<');
+        html.push_str(&tag_name);
+        for attribute in &self.node.attributes.items {
+            if let Some(serialized) = attribute.to_html() {
+                html.push(' ');
+                html.push_str(&serialized);
+            }
+        }
+        let self_close_custom = matches!(kind, NodeType::Custom(_))
+            && self.children.is_empty()
+            && options.empty_custom_element == EmptyCustomElement::SelfClose;
+        if kind.is_void() || self_close_custom {
+            html.push_str("/>");
+            return;
+        }
+        html.push('>');
+        for child in &self.children {
+            child.write_html(html, options);
+        }
+        html.push_str("</");
+        html.push_str(&tag_name);
+        html.push('>');
+    }
+}
+
 impl<'source> INode<'source> {
+    /// Every declaration styling this element, in the order they apply: its
+    /// [`cascaded_style`](INode::cascaded_style) when `<style>` rules match it, its inline styles
+    /// otherwise.
+    pub fn style_declarations(&self) -> impl DoubleEndedIterator<Item = &StyleDeclaration> {
+        let cascaded = self
+            .cascaded_style
+            .iter()
+            .flat_map(|style| &style.declarations);
+        let inline = self
+            .attributes
+            .items
+            .iter()
+            .filter_map(|attribute| match attribute {
+                Attribute::Style(style) if self.cascaded_style.is_none() => {
+                    Some(&style.declarations)
+                }
+                _ => None,
+            })
+            .flatten();
+        cascaded.chain(inline)
+    }
+
+    /// The `line-clamp` from this element's inline styles, the last declaration winning.
+    pub fn line_clamp(&self) -> Option<u32> {
+        self.style_declarations()
+            .filter_map(|declaration| match declaration {
+                StyleDeclaration::LineClamp(lines) => Some(*lines),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// The `font-family` from this element's inline styles, the last declaration winning.
+    pub fn font_family(&self) -> Option<&FontFamily> {
+        self.style_declarations()
+            .filter_map(|declaration| match declaration {
+                StyleDeclaration::FontFamily(families) => Some(families),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// The text `color` from this element's inline styles, the last declaration winning.
+    pub fn text_color(&self) -> Option<Color> {
+        self.style_declarations()
+            .filter_map(|declaration| match declaration {
+                StyleDeclaration::TextColor(color) => Some(*color),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// The `text-transform` from this element's inline styles, the last declaration winning.
+    pub fn text_transform(&self) -> Option<TextTransform> {
+        self.style_declarations()
+            .filter_map(|declaration| match declaration {
+                StyleDeclaration::TextTransform(transform) => Some(*transform),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// The `transition` from this element's inline styles, the last declaration winning.
+    pub fn transition(&self) -> Option<&TransitionSpec> {
+        self.style_declarations()
+            .filter_map(|declaration| match declaration {
+                StyleDeclaration::Transition(spec) => Some(spec),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// The `cursor` from this element's inline styles, the last declaration winning.
+    pub fn cursor(&self) -> Option<CursorStyle> {
+        self.style_declarations()
+            .filter_map(|declaration| match declaration {
+                StyleDeclaration::Cursor(cursor) => Some(*cursor),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// The element's own `font-size`, unresolved; see [`ITree::to_bevy_trees`] for inheritance.
+    ///
+    /// [`ITree::to_bevy_trees`]: crate::itree::ITree::to_bevy_trees
+    pub fn font_size(&self) -> Option<Val> {
+        self.style_declarations()
+            .filter_map(|declaration| match declaration {
+                StyleDeclaration::FontSize(size) => Some(*size),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// Toggle state for checkbox and radio inputs, `None` for every other element.
+    pub fn toggle_control(&self) -> Option<ToggleControl> {
+        if self.node_type != NodeType::Input {
+            return None;
+        }
+        let input_type = self
+            .attributes
+            .items
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Type(value) => Some(value.as_ref()),
+                _ => None,
+            })?;
+        let radio = if input_type.eq_ignore_ascii_case("radio") {
+            true
+        } else if input_type.eq_ignore_ascii_case("checkbox") {
+            false
+        } else {
+            return None;
+        };
+        let checked = self
+            .attributes
+            .items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::Checked(true)));
+        Some(ToggleControl { checked, radio })
+    }
+
+    /// The `box-shadow` from this element's inline styles, the last declaration winning.
+    pub fn box_shadow(&self) -> Option<BoxShadow> {
+        self.style_declarations()
+            .filter_map(|declaration| match declaration {
+                StyleDeclaration::BoxShadow(shadows) => Some(BoxShadow(shadows.clone())),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    /// Stacking order from `data-global-z`, letting overlays such as modals render above the whole
+    /// UI whatever their place in the hierarchy. Values that are not integers are ignored.
+    pub fn global_z_index(&self) -> Option<GlobalZIndex> {
+        let value = self
+            .attributes
+            .items
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Data { key, value } if key == "global-z" => Some(value.as_deref()),
+                _ => None,
+            })?;
+        match value.unwrap_or_default().trim().parse() {
+            Ok(z) => Some(GlobalZIndex(z)),
+            Err(err) => {
+                warn!("ignoring data-global-z {:?}: {}", value, err);
+                None
+            }
+        }
+    }
+
+    /// Range and value of a `<progress>` or `<meter>`, `None` for every other element. Progress
+    /// bars always start at zero and default to a maximum of one; a missing or invalid value reads
+    /// as the minimum.
+    pub fn progress_state(&self) -> Option<ProgressState> {
+        if !matches!(self.node_type, NodeType::Progress | NodeType::Meter) {
+            return None;
+        }
+        let (mut min, mut max, mut value) = (None, None, None);
+        for attribute in &self.attributes.items {
+            match attribute {
+                Attribute::Min(raw) => min = Some(raw.as_ref()),
+                Attribute::Max(raw) => max = Some(raw.as_ref()),
+                Attribute::Value(raw) => value = Some(raw.as_ref()),
+                _ => {}
+            }
+        }
+        let number = |raw: Option<&str>| {
+            raw.and_then(|raw| raw.trim().parse::<f32>().ok())
+                .filter(|number| number.is_finite())
+        };
+        let min = match self.node_type {
+            NodeType::Meter => number(min).unwrap_or(0.0),
+            _ => 0.0,
+        };
+        let max = number(max).filter(|max| *max > min).unwrap_or(min + 1.0);
+        let value = number(value).unwrap_or(min).clamp(min, max);
+        Some(ProgressState { value, min, max })
+    }
+
+    /// Span of a table cell, `None` for every other element. Missing, zero or invalid spans
+    /// count as 1, and spans are capped at the limits HTML sets.
+    pub fn cell_span(&self) -> Option<CellSpan> {
+        if !matches!(self.node_type, NodeType::Td | NodeType::Th) {
+            return None;
+        }
+        let (mut col, mut row) = (None, None);
+        for attribute in &self.attributes.items {
+            match attribute {
+                Attribute::ColSpan(raw) => col = Some(raw.as_ref()),
+                Attribute::RowSpan(raw) => row = Some(raw.as_ref()),
+                _ => {}
+            }
+        }
+        let span = |raw: Option<&str>, max: u32| {
+            raw.and_then(|raw| raw.trim().parse::<u32>().ok())
+                .filter(|span| *span > 0)
+                .map_or(1, |span| span.min(max))
+        };
+        Some(CellSpan {
+            col: span(col, 1000),
+            row: span(row, 65534),
+        })
+    }
+
+    /// State of this element's own `hidden` attribute.
+    pub fn hidden(&self) -> Hidden {
+        self.attributes
+            .items
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Hidden(hidden) => Some(*hidden),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
     pub fn to_bundle(&self) -> INodeBundle {
         let mut node = self.node_type.to_bevy_node();
+        if self.direction == Direction::Rtl && matches!(self.node_type, NodeType::Ul | NodeType::Ol)
+        {
+            // Markers sit on the inline-start side, which is the right edge for RTL lists.
+            std::mem::swap(&mut node.padding.left, &mut node.padding.right);
+        }
         let mut background_color = BackgroundColor::DEFAULT;
-        let border_color = BorderColor::DEFAULT;
-        apply_style_attributes(&self.attributes, &mut node, &mut background_color);
+        let mut border_color = BorderColor::DEFAULT;
+        if let Some(toggle) = self.toggle_control() {
+            node = toggle_node(toggle.radio);
+            border_color = BorderColor::all(Color::from(basic::GRAY));
+        }
+        if self.tag_alias.as_deref() == Some("center") {
+            node.display = Display::Flex;
+            node.flex_direction = FlexDirection::Column;
+            node.align_items = AlignItems::Center;
+        }
+        if let Some(span) = self.cell_span() {
+            // Cells share their row's width, so a spanning cell takes a proportionally larger share.
+            node.flex_grow = span.col as f32;
+        }
+        if self.hidden() == Hidden::Yes {
+            // Like the user agent stylesheet rule, so an inline `display` still wins.
+            node.display = Display::None;
+        }
+        // Inline `style` beats presentational attributes, which beat the element defaults above.
+        apply_presentational_dimensions(&self.attributes, &mut node);
+        apply_style_declarations(&mut node, &mut background_color, self.style_declarations());
         INodeBundle {
             id: self.id,
             name: Name::new(self.node_type.tag_name().into_owned()),
@@ -109,24 +818,47 @@ impl<'source> fmt::Debug for INode<'source> {
     }
 }
 
-fn apply_style_attributes<Str>(
-    attributes: &Attributes<Str>,
-    node: &mut Node,
-    background_color: &mut BackgroundColor,
-) {
+/// Applies `width`/`height` attributes, which stand in for the matching style properties.
+fn apply_presentational_dimensions<Str: AsRef<str>>(attributes: &Attributes<Str>, node: &mut Node) {
     for attribute in &attributes.items {
-        let Attribute::Style(style) = attribute else {
-            continue;
-        };
-        apply_style_declarations(node, background_color, &style.declarations);
+        match attribute {
+            Attribute::Width(value) => {
+                if let Some(width) = parse_dimension(value.as_ref()) {
+                    node.width = width;
+                }
+            }
+            Attribute::Height(value) => {
+                if let Some(height) = parse_dimension(value.as_ref()) {
+                    node.height = height;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads an HTML dimension value: a non-negative number of pixels, or a percentage when followed
+/// by `%`. Like browsers, trailing junk such as a `px` suffix is ignored; anything without leading
+/// digits yields `None`.
+fn parse_dimension(value: &str) -> Option<Val> {
+    let trimmed = value.trim();
+    let digits = trimmed
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(trimmed.len());
+    let number = trimmed[..digits].parse::<f32>().ok()?;
+    if trimmed[digits..].starts_with('%') {
+        Some(Val::Percent(number))
+    } else {
+        Some(Val::Px(number))
     }
 }
 
-fn apply_style_declarations(
+fn apply_style_declarations<'a>(
     node: &mut Node,
     background_color: &mut BackgroundColor,
-    declarations: &[StyleDeclaration],
+    declarations: impl IntoIterator<Item = &'a StyleDeclaration>,
 ) {
+    let mut opacity = None;
     for declaration in declarations {
         match declaration {
             StyleDeclaration::Display(value) => node.display = *value,
@@ -156,7 +888,7 @@ fn apply_style_declarations(
             StyleDeclaration::BorderTop(value) => node.border.top = *value,
             StyleDeclaration::BorderBottom(value) => node.border.bottom = *value,
             StyleDeclaration::BorderRadius(value) => node.border_radius = *value,
-            StyleDeclaration::BackgroundColor(value) => background_color.0 = value.clone(),
+            StyleDeclaration::BackgroundColor(value) => background_color.0 = *value,
             StyleDeclaration::AlignItems(value) => node.align_items = *value,
             StyleDeclaration::JustifyContent(value) => node.justify_content = *value,
             StyleDeclaration::RowGap(value) => node.row_gap = *value,
@@ -166,66 +898,69 @@ fn apply_style_declarations(
                 node.column_gap = *column;
             }
             StyleDeclaration::FlexBasis(value) => node.flex_basis = *value,
+            StyleDeclaration::AlignContent(value) => node.align_content = *value,
+            StyleDeclaration::AlignSelf(value) => node.align_self = *value,
+            StyleDeclaration::Position(value) => node.position_type = *value,
+            StyleDeclaration::FlexWrap(value) => node.flex_wrap = *value,
+            StyleDeclaration::Overflow(value) => node.overflow = *value,
+            StyleDeclaration::OverflowX(value) => node.overflow.x = *value,
+            StyleDeclaration::OverflowY(value) => node.overflow.y = *value,
+            StyleDeclaration::FlexGrow(value) => node.flex_grow = *value,
+            StyleDeclaration::AspectRatio(value) => node.aspect_ratio = Some(*value),
+            StyleDeclaration::FlexShrink(value) => node.flex_shrink = *value,
+            StyleDeclaration::FlexDirection(value) => node.flex_direction = *value,
+            StyleDeclaration::Opacity(value) => opacity = Some(*value),
+            StyleDeclaration::LineClamp(_)
+            | StyleDeclaration::Cursor(_)
+            | StyleDeclaration::FontFamily(_)
+            | StyleDeclaration::TextColor(_)
+            | StyleDeclaration::TextTransform(_)
+            | StyleDeclaration::Transition(_)
+            | StyleDeclaration::BoxShadow(_)
+            | StyleDeclaration::FontSize(_) => {}
         }
     }
+    // Bevy UI has no node-level opacity, so it is approximated by fading the background.
+    if let Some(opacity) = opacity {
+        let alpha = background_color.0.alpha();
+        background_color.0.set_alpha(alpha * opacity);
+    }
+}
+
+/// Invokes `$callback!` with every built-in element variant of [`NodeType`]. This is the one list
+/// the enum, [`NodeType::all_builtins`] and the marker components in `bevyml-components` are
+/// generated from, so a new element only has to be added here.
+#[macro_export]
+macro_rules! for_each_element_type {
+    ($callback:ident) => {
+        $callback! {
+            Html, Head, Body, Title, Meta, Link, Style, Script, NoScript, Div, Span, P, Pre, S,
+            Strong, B, Em, I, A, Img, Picture, Source, Button, Input, Label, Textarea, Select,
+            Option, Ul, Ol, Li, Table, Thead, Tbody, Tfoot, Tr, Th, Td, Header, Footer, Nav, Main,
+            Section, Article, Aside, Form, Canvas, Svg, Br, Wbr, Hr, Details, Summary, Progress,
+            Meter, H1, H2, H3, H4, H5, H6,
+        }
+    };
 }
 
-#[derive(Clone, PartialEq, Eq, Reflect, Debug, EnumString, AsRefStr)]
-#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
-pub enum NodeType {
-    Html,
-    Head,
-    Body,
-    Title,
-    Meta,
-    Link,
-    Style,
-    Script,
-    Div,
-    Span,
-    P,
-    A,
-    Img,
-    Button,
-    Input,
-    Label,
-    Textarea,
-    Select,
-    Option,
-    Ul,
-    Ol,
-    Li,
-    Table,
-    Thead,
-    Tbody,
-    Tfoot,
-    Tr,
-    Th,
-    Td,
-    Header,
-    Footer,
-    Nav,
-    Main,
-    Section,
-    Article,
-    Aside,
-    Form,
-    Canvas,
-    Svg,
-    Br,
-    Hr,
-    H1,
-    H2,
-    H3,
-    H4,
-    H5,
-    H6,
-    #[strum(serialize = "#text")]
-    Text,
-    #[strum(disabled)]
-    Custom(String),
+macro_rules! node_type_enum {
+    ($($variant:ident),* $(,)?) => {
+        #[derive(Clone, PartialEq, Eq, Hash, Reflect, Debug, EnumString, AsRefStr)]
+        #[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+        pub enum NodeType {
+            $($variant,)*
+            #[strum(serialize = "#text")]
+            Text,
+            #[strum(disabled)]
+            Custom(String),
+        }
+
+        const BUILTIN_NODE_TYPES: &[NodeType] = &[$(NodeType::$variant),*];
+    };
 }
 
+for_each_element_type!(node_type_enum);
+
 #[derive(Component, Clone, Debug, Reflect)]
 pub struct NodeKind {
     pub kind: NodeType,
@@ -236,6 +971,12 @@ impl NodeType {
         NodeType::from_str(tag_name).unwrap_or_else(|_| NodeType::Custom(tag_name.to_string()))
     }
 
+    /// Lists every built-in element type, i.e. everything but [`NodeType::Text`] and
+    /// [`NodeType::Custom`].
+    pub fn all_builtins() -> &'static [NodeType] {
+        BUILTIN_NODE_TYPES
+    }
+
     pub fn tag_name(&self) -> Cow<'_, str> {
         match self {
             NodeType::Custom(name) => Cow::Borrowed(name.as_str()),
@@ -243,6 +984,91 @@ impl NodeType {
         }
     }
 
+    /// Elements that get an `Interaction` when spawned, unless they sit in an inert subtree.
+    pub fn is_interactive(&self) -> bool {
+        matches!(
+            self,
+            NodeType::A
+                | NodeType::Button
+                | NodeType::Input
+                | NodeType::Select
+                | NodeType::Textarea
+        )
+    }
+
+    /// Elements that never have content or an end tag.
+    pub fn is_void(&self) -> bool {
+        matches!(
+            self,
+            NodeType::Meta
+                | NodeType::Link
+                | NodeType::Img
+                | NodeType::Source
+                | NodeType::Input
+                | NodeType::Br
+                | NodeType::Wbr
+                | NodeType::Hr
+        )
+    }
+
+    /// Inline elements that only style the text they wrap, so their content can join the
+    /// surrounding text as spans of a single `Text`.
+    pub fn is_phrasing(&self) -> bool {
+        matches!(
+            self,
+            NodeType::Span
+                | NodeType::S
+                | NodeType::Strong
+                | NodeType::B
+                | NodeType::Em
+                | NodeType::I
+        )
+    }
+
+    /// Elements whose text is drawn in a bold weight.
+    pub fn is_bold(&self) -> bool {
+        matches!(self, NodeType::Strong | NodeType::B)
+    }
+
+    /// `<ul>` and `<ol>`, the elements `<li>` items belong in.
+    pub fn is_list(&self) -> bool {
+        matches!(self, NodeType::Ul | NodeType::Ol)
+    }
+
+    /// `<thead>`, `<tbody>` and `<tfoot>`, which group the rows of a table.
+    pub fn is_table_section(&self) -> bool {
+        matches!(self, NodeType::Thead | NodeType::Tbody | NodeType::Tfoot)
+    }
+
+    /// `<th>` and `<td>`, which only make sense inside a `<tr>`.
+    pub fn is_table_cell(&self) -> bool {
+        matches!(self, NodeType::Th | NodeType::Td)
+    }
+
+    /// Elements whose content may include text, and so take text styles like color and font.
+    /// False for void elements and for structural containers that only hold other elements.
+    pub fn can_contain_text(&self) -> bool {
+        !self.is_void()
+            && !matches!(
+                self,
+                NodeType::Html
+                    | NodeType::Head
+                    | NodeType::Style
+                    | NodeType::Script
+                    | NodeType::Select
+                    | NodeType::Picture
+                    | NodeType::Ul
+                    | NodeType::Ol
+                    | NodeType::Table
+                    | NodeType::Thead
+                    | NodeType::Tbody
+                    | NodeType::Tfoot
+                    | NodeType::Tr
+                    | NodeType::Canvas
+                    | NodeType::Svg
+            )
+    }
+
     pub fn to_bevy_node(&self) -> Node {
         match self {
             NodeType::Html => block_node(),
@@ -251,7 +1077,13 @@ impl NodeType {
             | NodeType::Meta
             | NodeType::Link
             | NodeType::Style
-            | NodeType::Script => Node {
+            | NodeType::Script
+            // Sources only feed the `<img>` of their picture.
+            | NodeType::Source
+            // Its break opportunity is carried by the preceding text instead.
+            | NodeType::Wbr
+            // Options belong to their select's popup rather than the page flow.
+            | NodeType::Option => Node {
                 display: Display::None,
                 ..Default::default()
             },
@@ -263,6 +1095,8 @@ impl NodeType {
                 ..Default::default()
             },
             NodeType::Div
+            // Only reaches the UI when scripting is off, where it renders like any block.
+            | NodeType::NoScript
             | NodeType::Header
             | NodeType::Footer
             | NodeType::Nav
@@ -270,8 +1104,10 @@ impl NodeType {
             | NodeType::Section
             | NodeType::Article
             | NodeType::Aside
-            | NodeType::Form => block_node(),
-            NodeType::P => block_with_margin(BASE_FONT_PX),
+            | NodeType::Form
+            | NodeType::Details
+            | NodeType::Summary => block_node(),
+            NodeType::P | NodeType::Pre => block_with_margin(BASE_FONT_PX),
             NodeType::Ul | NodeType::Ol => Node {
                 display: Display::Block,
                 margin: margin_block(BASE_FONT_PX),
@@ -282,13 +1118,23 @@ impl NodeType {
                 ..Default::default()
             },
             NodeType::Li => block_node(),
-            NodeType::Table
-            | NodeType::Thead
-            | NodeType::Tbody
-            | NodeType::Tfoot
-            | NodeType::Tr
-            | NodeType::Th
-            | NodeType::Td => block_node(),
+            // Bevy has no table layout, so tables are approximated with flexbox: row groups
+            // stack vertically, rows lay their cells out horizontally and cells share the width.
+            NodeType::Table | NodeType::Thead | NodeType::Tbody | NodeType::Tfoot => Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            NodeType::Tr => Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                ..Default::default()
+            },
+            NodeType::Th | NodeType::Td => Node {
+                display: Display::Block,
+                flex_grow: 1.0,
+                ..Default::default()
+            },
             NodeType::Hr => Node {
                 display: Display::Block,
                 margin: margin_block(BASE_FONT_PX * 0.5),
@@ -296,6 +1142,11 @@ impl NodeType {
                 width: Val::Percent(100.0),
                 ..Default::default()
             },
+            NodeType::Progress | NodeType::Meter => Node {
+                width: Val::Px(BASE_FONT_PX * 10.0),
+                height: Val::Px(BASE_FONT_PX),
+                ..Default::default()
+            },
             NodeType::H1 => block_with_margin(BASE_FONT_PX * 0.67),
             NodeType::H2 => block_with_margin(BASE_FONT_PX * 0.83),
             NodeType::H3 => block_with_margin(BASE_FONT_PX),
@@ -308,7 +1159,24 @@ impl NodeType {
     }
 }
 
-const BASE_FONT_PX: f32 = 16.0;
+pub(crate) const BASE_FONT_PX: f32 = 16.0;
+const TOGGLE_SIZE_PX: f32 = 13.0;
+
+/// A small square box for checkboxes, rounded into a circle for radios.
+fn toggle_node(radio: bool) -> Node {
+    Node {
+        width: Val::Px(TOGGLE_SIZE_PX),
+        height: Val::Px(TOGGLE_SIZE_PX),
+        margin: UiRect::axes(Val::Px(4.0), Val::Px(3.0)),
+        border: UiRect::all(Val::Px(1.0)),
+        border_radius: if radio {
+            BorderRadius::MAX
+        } else {
+            BorderRadius::all(Val::Px(2.0))
+        },
+        ..Default::default()
+    }
+}
 
 fn block_node() -> Node {
     Node {
@@ -332,3 +1200,285 @@ fn margin_block(px: f32) -> UiRect {
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BevymlParser;
+
+    fn bevy_tree(source: &str) -> BevyNodeTree {
+        let trees: Vec<BevyNodeTree> = BevymlParser::new().parse(source).unwrap().into();
+        trees.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn table_rows_lay_out_as_flex_rows() {
+        let node = NodeType::Tr.to_bevy_node();
+        assert_eq!(node.display, Display::Flex);
+        assert_eq!(node.flex_direction, FlexDirection::Row);
+    }
+
+    #[test]
+    fn builtins_cover_every_element_and_round_trip_through_tag_names() {
+        let builtins = NodeType::all_builtins();
+        assert_eq!(builtins.len(), 61);
+        for node_type in builtins {
+            assert!(!matches!(node_type, NodeType::Text | NodeType::Custom(_)));
+            assert_eq!(&NodeType::from_tag_name(&node_type.tag_name()), node_type);
+        }
+    }
+
+    #[test]
+    fn options_default_to_display_none() {
+        assert_eq!(NodeType::Option.to_bevy_node().display, Display::None);
+    }
+
+    #[test]
+    fn structural_hash_tracks_structure() {
+        let source = r#"<div class="card"><p>Title</p><button>Go</button></div>"#;
+        assert_eq!(
+            bevy_tree(source).structural_hash(),
+            bevy_tree(source).structural_hash()
+        );
+        let modified = r#"<div class="card"><p>Title</p><button>Stop</button></div>"#;
+        assert_ne!(
+            bevy_tree(source).structural_hash(),
+            bevy_tree(modified).structural_hash()
+        );
+    }
+
+    #[test]
+    fn bundles_can_be_built_directly() {
+        let bundle = INodeBundle::new(NodeType::Button, NodeType::Button.to_bevy_node());
+        assert_eq!(bundle.node_kind.kind, NodeType::Button);
+        assert_eq!(bundle.name.as_str(), "button");
+        assert!(bundle.attributes.items.is_empty());
+
+        let renamed = bundle.with_name("submit");
+        assert_eq!(renamed.name.as_str(), "submit");
+    }
+
+    #[test]
+    fn to_html_escapes_text_and_attribute_values() {
+        let source = r#"<p title="a &quot;b&quot;">a&lt;b&amp;c</p>"#;
+        let tree = bevy_tree(source);
+        let text: String = tree
+            .children
+            .iter()
+            .filter_map(|child| child.text.as_ref())
+            .map(|text| text.0.as_str())
+            .collect();
+        assert_eq!(text, "a<b&c");
+        assert_eq!(tree.to_html(), source);
+    }
+
+    #[test]
+    fn empty_custom_elements_follow_the_close_policy() {
+        let tree = bevy_tree("<my-el></my-el>");
+        assert_eq!(tree.to_html(), "<my-el></my-el>");
+        let options = HtmlOptions {
+            empty_custom_element: EmptyCustomElement::SelfClose,
+        };
+        assert_eq!(tree.to_html_with(&options), "<my-el/>");
+    }
+
+    #[test]
+    fn translate_no_subtrees_flag_their_text() {
+        let tree = bevy_tree(r#"<div><p>Welcome</p><div translate="no"><p>Bevyml</p></div></div>"#);
+        let runs: Vec<_> = tree
+            .text_runs()
+            .into_iter()
+            .map(|run| (run.text, run.translate))
+            .collect();
+        assert_eq!(
+            runs,
+            [("Welcome".to_string(), true), ("Bevyml".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn text_containers_and_structural_elements() {
+        for node_type in [
+            NodeType::P,
+            NodeType::Span,
+            NodeType::H1,
+            NodeType::Label,
+            NodeType::Td,
+        ] {
+            assert!(node_type.can_contain_text(), "{node_type:?}");
+        }
+        for node_type in [
+            NodeType::Img,
+            NodeType::Hr,
+            NodeType::Br,
+            NodeType::Table,
+            NodeType::Ul,
+        ] {
+            assert!(!node_type.can_contain_text(), "{node_type:?}");
+        }
+    }
+
+    #[test]
+    fn data_global_z_yields_a_global_z_index() {
+        let tree = bevy_tree(r#"<div data-global-z="10"><p>modal</p></div>"#);
+        assert_eq!(tree.global_z_index.map(|z| z.0), Some(10));
+        assert!(bevy_tree("<div></div>").global_z_index.is_none());
+    }
+
+    #[test]
+    fn hidden_until_found_stays_laid_out_unlike_hidden() {
+        let tree = bevy_tree(r#"<div><p hidden>gone</p><p hidden="until-found">found</p></div>"#);
+        let (hidden, until_found) = (&tree.children[0], &tree.children[1]);
+        assert_eq!(hidden.node.node.display, Display::None);
+        assert!(!hidden.hidden_until_found);
+        assert_ne!(until_found.node.node.display, Display::None);
+        assert!(until_found.hidden_until_found);
+        let run = tree
+            .text_runs()
+            .into_iter()
+            .find(|run| run.text == "found")
+            .unwrap();
+        assert!(!run.visible);
+    }
+
+    #[test]
+    fn percent_font_size_scales_the_parent_and_em_the_root() {
+        let font_size = |source: &str| bevy_tree(source).text_runs()[0].font_size;
+        assert_eq!(
+            font_size(r#"<div style="font-size:20px"><span style="font-size:150%">x</span></div>"#),
+            Some(30.0)
+        );
+        assert_eq!(
+            font_size(r#"<div style="font-size:20px"><span style="font-size:2em">x</span></div>"#),
+            Some(2.0 * BASE_FONT_PX)
+        );
+    }
+
+    #[test]
+    fn closed_details_hide_their_body_but_not_the_summary() {
+        let displays = |source: &str| {
+            let tree = bevy_tree(source);
+            let displays: Vec<Display> = tree
+                .children
+                .iter()
+                .map(|child| child.node.node.display)
+                .collect();
+            (tree.disclosure, displays)
+        };
+        let (closed, closed_displays) =
+            displays("<details><summary>More</summary><p>body</p></details>");
+        assert_eq!(closed, Some(Disclosure { open: false }));
+        assert_ne!(closed_displays[0], Display::None);
+        assert_eq!(closed_displays[1], Display::None);
+        let (open, open_displays) =
+            displays("<details open><summary>More</summary><p>body</p></details>");
+        assert_eq!(open, Some(Disclosure { open: true }));
+        assert!(open_displays
+            .iter()
+            .all(|display| *display != Display::None));
+    }
+
+    #[test]
+    fn progress_value_becomes_a_clamped_fraction() {
+        let progress = bevy_tree(r#"<progress value="3" max="10"></progress>"#)
+            .progress
+            .unwrap();
+        assert_eq!(progress.fraction(), 0.3);
+        let overfull = bevy_tree(r#"<progress value="12" max="10"></progress>"#)
+            .progress
+            .unwrap();
+        assert_eq!(overfull.value, 10.0);
+        assert_eq!(overfull.fraction(), 1.0);
+    }
+
+    #[test]
+    fn table_cells_read_typed_spans() {
+        let tree =
+            bevy_tree(r#"<table><tr><td colspan="3" rowspan="2">a</td><td>b</td></tr></table>"#);
+        let row = &tree.children[0];
+        assert_eq!(row.children[0].cell_span, Some(CellSpan { col: 3, row: 2 }));
+        assert_eq!(row.children[1].cell_span, Some(CellSpan::default()));
+    }
+
+    #[test]
+    fn declared_font_size_reaches_the_text() {
+        let tree = bevy_tree(r#"<p style="font-size: 24px">big</p>"#);
+        assert_eq!(tree.text_runs()[0].font_size, Some(24.0));
+    }
+
+    #[test]
+    fn text_inherits_the_nearest_declared_color() {
+        let tree = bevy_tree(r#"<p style="color:blue">hello</p>"#);
+        assert_eq!(
+            tree.text_runs()[0].color,
+            Some(Color::Srgba(bevy_color::palettes::css::BLUE))
+        );
+        assert_eq!(bevy_tree("<p>plain</p>").text_runs()[0].color, None);
+    }
+
+    #[test]
+    fn flex_direction_column_reaches_the_node() {
+        let direction = |source: &str| bevy_tree(source).node.node.flex_direction;
+        assert_eq!(
+            direction(r#"<div style="display:flex; flex-direction:column">x</div>"#),
+            FlexDirection::Column
+        );
+        assert_eq!(
+            direction(r#"<div style="flex-direction:row-reverse">x</div>"#),
+            FlexDirection::RowReverse
+        );
+        assert_eq!(
+            direction(r#"<div style="flex-direction:diagonal">x</div>"#),
+            direction("<div>x</div>")
+        );
+    }
+
+    #[test]
+    fn inline_style_width_beats_the_width_attribute() {
+        let width = |source: &str| bevy_tree(source).node.node.width;
+        assert_eq!(
+            width(r#"<img width="100" style="width:50px" />"#),
+            Val::Px(50.0)
+        );
+        assert_eq!(width(r#"<img width="100" />"#), Val::Px(100.0));
+    }
+
+    #[test]
+    fn position_absolute_places_the_node_by_its_offsets() {
+        let node = bevy_tree(r#"<div style="position:absolute; top:10px; left:10px">x</div>"#)
+            .node
+            .node;
+        assert_eq!(node.position_type, bevy_ui::PositionType::Absolute);
+        assert_eq!((node.top, node.left), (Val::Px(10.0), Val::Px(10.0)));
+        let fixed = bevy_tree(r#"<div style="position:fixed">x</div>"#)
+            .node
+            .node;
+        assert_eq!(fixed.position_type, bevy_ui::PositionType::Absolute);
+        let relative = bevy_tree(r#"<div style="position:static">x</div>"#)
+            .node
+            .node;
+        assert_eq!(relative.position_type, bevy_ui::PositionType::Relative);
+    }
+
+    #[test]
+    fn overflow_accepts_one_or_two_values_and_per_axis_overrides() {
+        let overflow = |style: &str| {
+            bevy_tree(&format!(r#"<div style="{style}">x</div>"#))
+                .node
+                .node
+                .overflow
+        };
+        assert_eq!(overflow("overflow: hidden"), bevy_ui::Overflow::hidden());
+        assert_eq!(
+            overflow("overflow: visible scroll"),
+            bevy_ui::Overflow {
+                x: bevy_ui::OverflowAxis::Visible,
+                y: bevy_ui::OverflowAxis::Scroll,
+            }
+        );
+        assert_eq!(
+            overflow("overflow-y: scroll").y,
+            bevy_ui::OverflowAxis::Scroll
+        );
+    }
+}
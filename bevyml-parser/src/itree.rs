@@ -1,18 +1,41 @@
-use bevy_log::debug;
-use bevy_ui::widget::Text;
+use bevy_color::Color;
+use bevy_log::{debug, warn};
+use bevy_ui::{widget::Text, AlignItems, Display, JustifyContent, Node, Val};
 
 use crate::{
-    attributes::Attributes,
-    inode::{BevyNodeTree, INode, NodeId, NodeType, TextPosition},
-    tree_sitter::{Node as TsNode, Tree},
+    attributes::{
+        event_name, Attribute, AttributeOptions, Attributes, FontFamily, Hidden, StyleAttribute,
+        StyleContext, TextTransform,
+    },
+    entities::decode_entities,
+    inode::{
+        BevyNodeTree, CheckedStyle, ColorSchemeStyle, Direction, DisabledStyle, Disclosure,
+        DisclosureBody, HoverStyle, INode, ImageSource, MaxLines, NodeId, NodeType, StateColors,
+        TextPosition, TextSection, BASE_FONT_PX,
+    },
+    settings::ParseSettings,
+    stylesheet::{ColorScheme, PseudoClass, Stylesheet},
+    tree_sitter::{Node as TsNode, Point, Tree},
+};
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    fmt::{self, Write},
+    ops::Range,
 };
-use std::{borrow::Cow, convert::TryFrom, fmt};
 
 /// Intermediary Tree
 pub struct ITree<'source> {
     pub roots: Vec<NodeId>,
     pub nodes: Vec<INode<'source>>,
     pub child_indices: Vec<NodeId>,
+    /// Number of `ERROR` and `MISSING` nodes tree-sitter produced while recovering from bad markup.
+    pub error_node_count: usize,
+    source: &'source str,
+    /// Copied from [`ParseSettings::css_flex_alignment`].
+    css_flex_alignment: bool,
+    /// Copied from [`ParseSettings::root_font_size`].
+    root_font_px: f32,
 }
 
 impl<'source> fmt::Debug for ITree<'source> {
@@ -21,6 +44,7 @@ impl<'source> fmt::Debug for ITree<'source> {
             .field("roots", &self.roots)
             .field("nodes_len", &self.nodes.len())
             .field("edges_len", &self.child_indices.len())
+            .field("error_node_count", &self.error_node_count)
             .finish()
     }
 }
@@ -46,29 +70,55 @@ impl<'source> TryFrom<(&Tree, &'source str)> for ITree<'source> {
     type Error = ITreeError;
 
     fn try_from((tree, source): (&Tree, &'source str)) -> Result<Self, Self::Error> {
-        let mut itree = ITree::new();
-        let roots = collect_root_elements(tree.root_node(), source, &mut itree);
+        ITree::try_from((tree, source, &ParseSettings::default()))
+    }
+}
+
+impl<'source> TryFrom<(&Tree, &'source str, &ParseSettings)> for ITree<'source> {
+    type Error = ITreeError;
+
+    fn try_from(
+        (tree, source, settings): (&Tree, &'source str, &ParseSettings),
+    ) -> Result<Self, Self::Error> {
+        let mut itree = ITree::new(source);
+        itree.css_flex_alignment = settings.css_flex_alignment;
+        itree.root_font_px = settings.root_font_size;
+        let mut roots = collect_root_elements(tree.root_node(), source, settings, &mut itree);
+        if roots.is_empty() && settings.allow_text_root {
+            roots.extend(build_implicit_root(
+                tree.root_node(),
+                source,
+                settings,
+                &mut itree,
+            ));
+        }
         if roots.is_empty() {
             return Err(ITreeError::MissingRootElement);
         }
 
         itree.roots = roots;
+        itree.error_node_count = count_error_nodes(tree.root_node());
+        apply_style_elements(&mut itree, settings);
         Ok(itree)
     }
 }
 
-impl<'source> Into<Vec<BevyNodeTree>> for ITree<'source> {
-    fn into(self) -> Vec<BevyNodeTree> {
-        self.into_bevy_trees()
+impl<'source> From<ITree<'source>> for Vec<BevyNodeTree> {
+    fn from(itree: ITree<'source>) -> Self {
+        itree.into_bevy_trees()
     }
 }
 
 impl<'source> ITree<'source> {
-    fn new() -> Self {
+    fn new(source: &'source str) -> Self {
         Self {
             roots: Vec::new(),
             nodes: Vec::new(),
             child_indices: Vec::new(),
+            error_node_count: 0,
+            source,
+            css_flex_alignment: false,
+            root_font_px: BASE_FONT_PX,
         }
     }
 
@@ -81,11 +131,79 @@ impl<'source> ITree<'source> {
         &self.child_indices[range]
     }
 
+    /// The exact markup `id` was parsed from, spanning `start_byte..end_byte` of the input.
+    pub fn source_text(&self, id: NodeId) -> &'source str {
+        self.node(id).original_text
+    }
+
+    /// Row and column of an arbitrary byte offset into the parsed input, for diagnostics that
+    /// point between nodes.
+    pub fn position_of(&self, byte: usize) -> TextPosition {
+        TextPosition::of_byte(self.source, byte)
+    }
+
+    /// Yields the children of `id` as `INode` references instead of bare ids.
+    pub fn child_nodes(&self, id: NodeId) -> impl Iterator<Item = &INode<'source>> + '_ {
+        self.children(id)
+            .iter()
+            .map(|child_id| self.node(*child_id))
+    }
+
+    /// Walks every node in document order, yielding its id, the node and its depth from the roots.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &INode<'source>, usize)> + '_ {
+        DepthFirstIter {
+            itree: self,
+            stack: self.roots.iter().rev().map(|root| (*root, 0)).collect(),
+        }
+    }
+
     /// Prints a readable representation of the tree as seen in the CLI helper.
     pub fn pretty_print(&self) {
         self.print_nodes(&self.roots, 0);
     }
 
+    /// Like [`ITree::pretty_print`], but also lists every node's attributes and the inline style
+    /// declarations that could not be parsed.
+    pub fn pretty_print_verbose(&self) {
+        print!("{}", self.pretty_string_verbose());
+    }
+
+    /// The text [`ITree::pretty_print_verbose`] prints.
+    pub fn pretty_string_verbose(&self) -> String {
+        let mut out = String::new();
+        for (_, node, depth) in self.iter() {
+            let indent = "  ".repeat(depth);
+            let tag_name = node.node_type.tag_name();
+            let element_name = if tag_name.as_ref() == "unknown" {
+                "<unknown>"
+            } else {
+                tag_name.as_ref()
+            };
+            // Writing into a `String` cannot fail.
+            let _ = writeln!(
+                out,
+                "{}- node_type={:?} element={} simplified_content={:?}",
+                indent,
+                node.node_type,
+                element_name,
+                node.simplified_content.as_ref()
+            );
+            for attribute in &node.attributes.items {
+                let _ = writeln!(out, "{}    attribute {:?}", indent, attribute);
+                if let Attribute::Style(style) = attribute {
+                    for unsupported in &style.unsupported {
+                        let _ = writeln!(
+                            out,
+                            "{}    unsupported style {}: {}",
+                            indent, unsupported.property, unsupported.value
+                        );
+                    }
+                }
+            }
+        }
+        out
+    }
+
     /// Logs the same tree layout via Bevy's logging at the `debug` level.
     pub fn pretty_log(&self) {
         self.log_nodes(&self.roots, 0);
@@ -136,30 +254,531 @@ impl<'source> ITree<'source> {
     }
 
     fn into_bevy_trees(self) -> Vec<BevyNodeTree> {
-        let mut nodes: Vec<Option<INode<'source>>> = self.nodes.into_iter().map(Some).collect();
-        let child_indices = self.child_indices;
+        self.to_bevy_trees()
+    }
+
+    /// Builds the Bevy trees without consuming the `ITree`, so it stays available for queries.
+    pub fn to_bevy_trees(&self) -> Vec<BevyNodeTree> {
         self.roots
-            .into_iter()
-            .map(|root| build_bevy_tree(root, &mut nodes, &child_indices))
+            .iter()
+            .map(|root| self.build_bevy_tree(*root))
             .collect()
     }
+
+    /// Path from the root to `id`, each step indexed among same-tag siblings starting at 1, so
+    /// repeated tags stay distinguishable in entity names.
+    #[cfg(feature = "debug-names")]
+    fn debug_path(&self, id: NodeId) -> String {
+        let mut segments = Vec::new();
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            let inode = self.node(node_id);
+            let siblings = match inode.parent {
+                Some(parent) => self.children(parent),
+                None => &self.roots,
+            };
+            let index = siblings
+                .iter()
+                .take_while(|sibling| **sibling != node_id)
+                .filter(|sibling| self.node(**sibling).node_type == inode.node_type)
+                .count()
+                + 1;
+            segments.push(format!("{}[{}]", inode.node_type.tag_name(), index));
+            current = inode.parent;
+        }
+        segments.reverse();
+        segments.join(" > ")
+    }
+
+    /// What an `<img>` shows: inside a `<picture>`, the first candidate of the first `<source>`
+    /// carrying a `srcset`, otherwise its own `src`.
+    fn image_source(&self, id: NodeId) -> Option<ImageSource> {
+        let inode = self.node(id);
+        if inode.node_type != NodeType::Img {
+            return None;
+        }
+        let from_picture = inode
+            .parent
+            .filter(|parent| self.node(*parent).node_type == NodeType::Picture)
+            .and_then(|parent| {
+                self.child_nodes(parent)
+                    .take_while(|sibling| sibling.id != id)
+                    .filter(|sibling| sibling.node_type == NodeType::Source)
+                    .find_map(|source| {
+                        source
+                            .attributes
+                            .items
+                            .iter()
+                            .find_map(|attribute| match attribute {
+                                Attribute::SrcSet(srcset) => first_srcset_url(srcset),
+                                _ => None,
+                            })
+                    })
+            });
+        let src = || {
+            inode
+                .attributes
+                .items
+                .iter()
+                .find_map(|attribute| match attribute {
+                    Attribute::Src(src) if !src.trim().is_empty() => Some(src.trim()),
+                    _ => None,
+                })
+        };
+        from_picture
+            .or_else(src)
+            .map(|url| ImageSource(url.to_string()))
+    }
+
+    /// The `font-family` declared on `id` or its closest ancestor declaring one.
+    fn inherited_font_family(&self, id: NodeId) -> Option<FontFamily> {
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            let inode = self.node(node_id);
+            if let Some(families) = inode.font_family() {
+                return Some(families.clone());
+            }
+            current = inode.parent;
+        }
+        None
+    }
+
+    /// The `text-transform` declared on `id` or its closest ancestor declaring one, where an
+    /// explicit `none` stops an inherited transform.
+    fn inherited_text_transform(&self, id: NodeId) -> TextTransform {
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            let inode = self.node(node_id);
+            if let Some(transform) = inode.text_transform() {
+                return transform;
+            }
+            current = inode.parent;
+        }
+        TextTransform::None
+    }
+
+    /// The text `color` declared on `id` or its closest ancestor declaring one.
+    fn inherited_text_color(&self, id: NodeId) -> Option<Color> {
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            let inode = self.node(node_id);
+            if let Some(color) = inode.text_color() {
+                return Some(color);
+            }
+            current = inode.parent;
+        }
+        None
+    }
+
+    fn inherited_bold(&self, id: NodeId) -> bool {
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            let inode = self.node(node_id);
+            if inode.node_type.is_bold() {
+                return true;
+            }
+            current = inode.parent;
+        }
+        false
+    }
+
+    /// Whether `id`'s content is only text and shown phrasing elements, with at least one of the
+    /// latter, so it can be spawned as a single `Text` split into spans.
+    fn has_rich_text(&self, id: NodeId) -> bool {
+        let node_type = &self.node(id).node_type;
+        if *node_type == NodeType::Text || !node_type.can_contain_text() {
+            return false;
+        }
+        let mut has_inline_element = false;
+        self.children(id)
+            .iter()
+            .all(|child| self.is_inline_content(*child, &mut has_inline_element))
+            && has_inline_element
+    }
+
+    /// Sections `id` spawns as when [`ITree::has_rich_text`] holds, empty otherwise. Text nodes are
+    /// trimmed on their own, so a space is put back wherever the source has whitespace between
+    /// two of them.
+    fn text_sections(&self, id: NodeId) -> Vec<TextSection> {
+        if !self.has_rich_text(id) {
+            return Vec::new();
+        }
+        let mut text_ids = Vec::new();
+        self.collect_text_ids(id, &mut text_ids);
+        let mut sections: Vec<TextSection> = Vec::with_capacity(text_ids.len());
+        let mut trailing_space = false;
+        for text_id in text_ids {
+            let inode = self.node(text_id);
+            let Some(mut text) = self.layout_text(text_id) else {
+                continue;
+            };
+            let leading_space = inode
+                .original_text
+                .starts_with(|ch: char| ch.is_ascii_whitespace());
+            // The space stays with the text it was written next to, as in `a <b>b</b> c`.
+            if let Some(previous) = sections.last_mut()
+                && !previous.text.ends_with(' ')
+                && !text.starts_with(' ')
+            {
+                if trailing_space {
+                    previous.text.push(' ');
+                } else if leading_space {
+                    text.insert(0, ' ');
+                }
+            }
+            trailing_space = inode
+                .original_text
+                .ends_with(|ch: char| ch.is_ascii_whitespace());
+            let previous_char = sections
+                .last()
+                .and_then(|section| section.text.chars().next_back());
+            sections.push(TextSection {
+                text: self
+                    .inherited_text_transform(text_id)
+                    .apply_after(&text, previous_char),
+                bold: self.inherited_bold(text_id),
+                color: self.inherited_text_color(text_id),
+                font_size: self.computed_font_size(text_id),
+                node_id: text_id,
+            });
+        }
+        sections
+    }
+
+    fn collect_text_ids(&self, id: NodeId, text_ids: &mut Vec<NodeId>) {
+        for child in self.children(id) {
+            if self.node(*child).node_type == NodeType::Text {
+                if !self.is_layout_whitespace(*child) {
+                    text_ids.push(*child);
+                }
+            } else {
+                self.collect_text_ids(*child, text_ids);
+            }
+        }
+    }
+
+    fn is_inline_content(&self, id: NodeId, has_inline_element: &mut bool) -> bool {
+        let inode = self.node(id);
+        if inode.node_type == NodeType::Text {
+            return true;
+        }
+        if !inode.node_type.is_phrasing() || inode.hidden() != Hidden::No {
+            return false;
+        }
+        *has_inline_element = true;
+        self.children(id)
+            .iter()
+            .all(|child| self.is_inline_content(*child, has_inline_element))
+    }
+
+    /// Font size `id` inherits or declares, in pixels. Percentages scale the parent's computed
+    /// size, falling back to the root font size at the top; `None` when nothing on the ancestor
+    /// chain declares a usable size.
+    fn computed_font_size(&self, id: NodeId) -> Option<f32> {
+        let mut factor = None;
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            let inode = self.node(node_id);
+            match inode.font_size() {
+                Some(Val::Px(px)) => return Some(px * factor.unwrap_or(1.0)),
+                Some(Val::Percent(percent)) => {
+                    factor = Some(factor.unwrap_or(1.0) * percent / 100.0);
+                }
+                _ => {}
+            }
+            current = inode.parent;
+        }
+        factor.map(|factor| self.root_font_px * factor)
+    }
+
+    /// The `Node` `id` spawns with under `settings`: its element defaults, its inline styles, and
+    /// what the tree around it implies, like a closed `<details>` hiding its body. Settings that
+    /// shape values while parsing, such as `px_scale` or `root_font_size`, were fixed when the
+    /// tree was built; `settings` contributes the ones applied on output, like
+    /// [`ParseSettings::css_flex_alignment`].
+    pub fn computed_node(&self, id: NodeId, settings: &ParseSettings) -> Node {
+        let inode = self.node(id);
+        let mut node = inode.to_bundle().node;
+        apply_css_flex_alignment(&mut node, settings.css_flex_alignment);
+        if let Some(parent) = inode.parent
+            && self.hidden_by_closed_details(parent, id)
+        {
+            node.display = Display::None;
+        }
+        node
+    }
+
+    fn hidden_by_closed_details(&self, parent: NodeId, id: NodeId) -> bool {
+        let details = self.node(parent);
+        let closed = details.node_type == NodeType::Details
+            && !details
+                .attributes
+                .items
+                .iter()
+                .any(|attribute| matches!(attribute, Attribute::Open(true)));
+        closed
+            && self
+                .child_nodes(parent)
+                .find(|child| child.node_type == NodeType::Summary)
+                .is_none_or(|summary| summary.id != id)
+    }
+
+    /// Text of a text node as it is laid out. Raw text kept by `preserve_raw_text` still
+    /// collapses outside `<pre>`.
+    fn layout_text(&self, id: NodeId) -> Option<String> {
+        let inode = self.node(id);
+        inode.text.as_ref().map(|content| {
+            if inside_pre(self, inode.parent) {
+                content.to_string()
+            } else {
+                collapse_whitespace(content).into_owned()
+            }
+        })
+    }
+
+    /// Whitespace-only text outside `<pre>`, which only exists with `preserve_raw_text` and has
+    /// no place in the spawned layout.
+    fn is_layout_whitespace(&self, id: NodeId) -> bool {
+        let inode = self.node(id);
+        inode.node_type == NodeType::Text
+            && inode
+                .text
+                .as_ref()
+                .is_some_and(|text| text.trim_ascii().is_empty())
+            && !inside_pre(self, inode.parent)
+    }
+
+    fn build_bevy_tree(&self, id: NodeId) -> BevyNodeTree {
+        let inode = self.node(id);
+        let mut children: Vec<_> = self
+            .children(id)
+            .iter()
+            .filter(|child_id| !self.is_layout_whitespace(**child_id))
+            .map(|child_id| self.build_bevy_tree(*child_id))
+            .collect();
+        let disclosure = (inode.node_type == NodeType::Details).then(|| Disclosure {
+            open: inode
+                .attributes
+                .items
+                .iter()
+                .any(|attribute| matches!(attribute, Attribute::Open(true))),
+        });
+        if let Some(disclosure) = disclosure {
+            mark_disclosure_body(&mut children, disclosure.open);
+        }
+        let text = self
+            .layout_text(id)
+            .map(|text| Text::new(self.inherited_text_transform(id).apply(&text)));
+        // Text nodes carry no attributes of their own, so the clamp comes from the enclosing element.
+        let max_lines = text
+            .as_ref()
+            .and(inode.parent)
+            .and_then(|parent| self.node(parent).line_clamp())
+            .map(MaxLines);
+        let mut node = inode.to_bundle();
+        apply_css_flex_alignment(&mut node.node, self.css_flex_alignment);
+        #[cfg(feature = "debug-names")]
+        {
+            node.name = bevy_ecs::name::Name::new(self.debug_path(id));
+        }
+        BevyNodeTree {
+            node,
+            text,
+            inert: inode.inert,
+            no_translate: inode.no_translate,
+            // Inert content is not focusable, so its autofocus is dropped here.
+            autofocus: !inode.inert
+                && inode
+                    .attributes
+                    .items
+                    .iter()
+                    .any(|attribute| matches!(attribute, Attribute::Autofocus(true))),
+            max_lines,
+            cursor: inode.cursor(),
+            box_shadow: inode.box_shadow(),
+            toggle: inode.toggle_control(),
+            global_z_index: inode.global_z_index(),
+            hidden_until_found: inode.hidden() == Hidden::UntilFound,
+            font_size: if inode.node_type.can_contain_text() {
+                self.computed_font_size(id)
+            } else {
+                None
+            },
+            image_source: self.image_source(id),
+            disclosure,
+            disclosure_body: None,
+            progress: inode.progress_state(),
+            cell_span: inode.cell_span(),
+            font_family: if inode.node_type.can_contain_text() {
+                self.inherited_font_family(id)
+            } else {
+                None
+            },
+            text_color: if inode.node_type.can_contain_text() {
+                self.inherited_text_color(id)
+            } else {
+                None
+            },
+            transition: inode.transition().cloned(),
+            hover_style: inode
+                .hover_style
+                .as_ref()
+                .and_then(StateColors::from_style)
+                .map(HoverStyle),
+            checked_style: inode
+                .checked_style
+                .as_ref()
+                .and_then(StateColors::from_style)
+                .map(CheckedStyle),
+            disabled_style: inode
+                .disabled_style
+                .as_ref()
+                .and_then(StateColors::from_style)
+                .map(DisabledStyle),
+            color_scheme_style: (inode.light_style.is_some() || inode.dark_style.is_some()).then(
+                || ColorSchemeStyle {
+                    light: inode
+                        .light_style
+                        .as_ref()
+                        .and_then(StateColors::from_style)
+                        .unwrap_or_default(),
+                    dark: inode
+                        .dark_style
+                        .as_ref()
+                        .and_then(StateColors::from_style)
+                        .unwrap_or_default(),
+                },
+            ),
+            disabled: inode.node_type.is_interactive()
+                && inode
+                    .attributes
+                    .items
+                    .iter()
+                    .any(|attribute| matches!(attribute, Attribute::Disabled(true))),
+            bold: inode.node_type.can_contain_text() && self.inherited_bold(id),
+            text_sections: self.text_sections(id),
+            children,
+        }
+    }
+}
+
+/// Explicit-stack traversal backing [`ITree::iter`] so deep documents cannot overflow the call stack.
+struct DepthFirstIter<'tree, 'source> {
+    itree: &'tree ITree<'source>,
+    stack: Vec<(NodeId, usize)>,
+}
+
+impl<'tree, 'source> Iterator for DepthFirstIter<'tree, 'source> {
+    type Item = (NodeId, &'tree INode<'source>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (id, depth) = self.stack.pop()?;
+        let children = self.itree.children(id);
+        self.stack
+            .extend(children.iter().rev().map(|child| (*child, depth + 1)));
+        Some((id, self.itree.node(id), depth))
+    }
 }
 
 fn build_ui_node<'tree, 'source>(
     node: TsNode<'tree>,
     source: &'source str,
+    settings: &ParseSettings,
     itree: &mut ITree<'source>,
     parent: Option<NodeId>,
-) -> NodeId {
+) -> Option<NodeId> {
     let (info_node, is_self_closing) = resolve_element_node(node);
-    let node_type = extract_tag_name(info_node, source)
-        .as_deref()
-        .map(NodeType::from_tag_name)
-        .unwrap_or_else(|| NodeType::Custom("unknown".to_string()));
-    let attributes = extract_attributes(info_node, source);
+    let children = if is_self_closing {
+        Vec::new()
+    } else {
+        let mut cursor = node.walk();
+        let mut children: Vec<_> = node.children(&mut cursor).collect();
+        // The element's own start tag stays out of recovery so it is not taken for a dangling one.
+        let start_tag = (children.first().map(|child| child.kind()) == Some("start_tag"))
+            .then(|| children.remove(0));
+        start_tag
+            .map(Child::Node)
+            .into_iter()
+            .chain(recover_children(children, source))
+            .collect()
+    };
     let start = info_node.start_position();
     let end = info_node.end_position();
-    let original_text = extract_text_slice(info_node, source);
+    build_element(
+        ElementParts {
+            info_node,
+            text_root: node,
+            is_self_closing,
+            children,
+            bytes: info_node.start_byte()..info_node.end_byte(),
+            points: (start, end),
+        },
+        source,
+        settings,
+        itree,
+        parent,
+    )
+}
+
+/// What [`build_element`] needs to know about an element, whether it comes straight from the parse
+/// tree or was rebuilt by [`recover_children`].
+struct ElementParts<'tree> {
+    /// Holds the tag name and attributes: an element, a self-closing element or a lone start tag.
+    info_node: TsNode<'tree>,
+    /// Scanned for the first strong character when the element has `dir="auto"`.
+    text_root: TsNode<'tree>,
+    is_self_closing: bool,
+    children: Vec<Child<'tree>>,
+    bytes: Range<usize>,
+    points: (Point, Point),
+}
+
+fn build_element<'tree, 'source>(
+    parts: ElementParts<'tree>,
+    source: &'source str,
+    settings: &ParseSettings,
+    itree: &mut ITree<'source>,
+    parent: Option<NodeId>,
+) -> Option<NodeId> {
+    let ElementParts {
+        info_node,
+        text_root,
+        is_self_closing,
+        children,
+        bytes,
+        points: (start, end),
+    } = parts;
+    let tag_name = extract_tag_name(info_node, source);
+    let alias_target = tag_name.as_deref().and_then(|tag| settings.tag_alias(tag));
+    let node_type = alias_target
+        .or(tag_name.as_deref())
+        .map(NodeType::from_tag_name)
+        .unwrap_or_else(|| NodeType::Custom("unknown".to_string()));
+    let tag_alias = alias_target.and(tag_name.map(|tag| tag.to_ascii_lowercase()));
+    if node_type == NodeType::Script && !settings.allow_scripts {
+        debug!("skipping <script> subtree at byte {}", bytes.start);
+        return None;
+    }
+    if node_type == NodeType::NoScript && settings.scripting_enabled {
+        debug!("skipping <noscript> subtree at byte {}", bytes.start);
+        return None;
+    }
+    let attributes = extract_attributes(info_node, source, settings);
+    let direction = resolve_direction(&attributes, text_root, source, itree, parent);
+    let inert = inherited_inert(itree, parent)
+        || attributes
+            .items
+            .iter()
+            .any(|attribute| matches!(attribute, Attribute::Inert(true)));
+    let no_translate = attributes
+        .items
+        .iter()
+        .find_map(|attribute| match attribute {
+            Attribute::Translate(translate) => Some(!translate),
+            _ => None,
+        })
+        .unwrap_or_else(|| inherited_no_translate(itree, parent));
+    let original_text = source.get(bytes.clone()).unwrap_or_default();
     let simplified_content = if is_self_closing || info_node.kind() != "element" {
         Cow::Borrowed(original_text)
     } else {
@@ -170,8 +789,8 @@ fn build_ui_node<'tree, 'source>(
         id,
         node_type,
         attributes,
-        start_byte: info_node.start_byte(),
-        end_byte: info_node.end_byte(),
+        start_byte: bytes.start,
+        end_byte: bytes.end,
         start_position: TextPosition::new(start.column, start.row),
         end_position: TextPosition::new(end.column, end.row),
         simplified_content,
@@ -180,83 +799,652 @@ fn build_ui_node<'tree, 'source>(
         parent,
         children: 0..0,
         text: None,
+        list_ordinal: None,
+        direction,
+        inert,
+        no_translate,
+        tag_alias,
+        cascaded_style: None,
+        hover_style: None,
+        checked_style: None,
+        disabled_style: None,
+        light_style: None,
+        dark_style: None,
     });
 
+    // Descendants push their own edges while recursing, so gather this node's children first and
+    // append them as one contiguous run afterwards.
+    let mut child_ids = Vec::new();
+    let mut previous_end = None;
+    for child in children {
+        if settings.preserve_raw_text
+            && let Some(previous_end) = previous_end.replace(child.end())
+        {
+            child_ids.extend(build_gap_text_node(
+                previous_end,
+                child.start(),
+                source,
+                itree,
+                id,
+            ));
+        }
+        let child_id = build_child(child, source, settings, itree, Some(id));
+        if !settings.preserve_raw_text
+            && let Some(wbr) = child_id
+            && itree.node(wbr).node_type == NodeType::Wbr
+        {
+            mark_word_break(itree, child_ids.last().copied());
+        }
+        child_ids.extend(child_id);
+    }
     let child_start = itree.child_indices.len();
-    if !is_self_closing {
+    itree.child_indices.extend(child_ids);
+    let child_end = itree.child_indices.len();
+    itree.nodes[id.index()].children = child_start..child_end;
+    if itree.nodes[id.index()].node_type == NodeType::Ol {
+        number_list_items(itree, id);
+    }
+    Some(id)
+}
+
+fn build_child<'tree, 'source>(
+    child: Child<'tree>,
+    source: &'source str,
+    settings: &ParseSettings,
+    itree: &mut ITree<'source>,
+    parent: Option<NodeId>,
+) -> Option<NodeId> {
+    match child {
+        Child::Node(node) if is_element(node) => {
+            build_ui_node(node, source, settings, itree, parent)
+        }
+        Child::Node(node) if is_text_node(node) => {
+            build_text_node(node, source, settings, itree, parent)
+        }
+        Child::Node(_) => None,
+        Child::Recovered(recovered) => {
+            let bytes = recovered.start_tag.start_byte()..recovered.end.end_byte();
+            let points = (
+                recovered.start_tag.start_position(),
+                recovered.end.end_position(),
+            );
+            build_element(
+                ElementParts {
+                    info_node: recovered.start_tag,
+                    text_root: recovered.start_tag,
+                    is_self_closing: recovered.children.is_empty(),
+                    children: recovered.children,
+                    bytes,
+                    points,
+                },
+                source,
+                settings,
+                itree,
+                parent,
+            )
+        }
+    }
+}
+
+/// A node to build children from: one of the parse tree as is, or an element [`recover_children`]
+/// put back together.
+enum Child<'tree> {
+    Node(TsNode<'tree>),
+    Recovered(RecoveredElement<'tree>),
+}
+
+/// An element rebuilt from a start tag tree-sitter could not pair with its content, spanning from
+/// that tag to `end`.
+struct RecoveredElement<'tree> {
+    start_tag: TsNode<'tree>,
+    children: Vec<Child<'tree>>,
+    end: TsNode<'tree>,
+}
+
+impl<'tree> Child<'tree> {
+    fn start(&self) -> (usize, Point) {
+        let node = match self {
+            Child::Node(node) => node,
+            Child::Recovered(recovered) => &recovered.start_tag,
+        };
+        (node.start_byte(), node.start_position())
+    }
+
+    fn end(&self) -> (usize, Point) {
+        let node = match self {
+            Child::Node(node) => node,
+            Child::Recovered(recovered) => &recovered.end,
+        };
+        (node.end_byte(), node.end_position())
+    }
+}
+
+/// Rebuilds the structure tree-sitter loses around void elements written without a closing slash,
+/// like `<p>long<wbr>word</p>`. The grammar only knows paired and self-closing tags, so it pairs
+/// `<wbr>` with `</p>` and leaves `<p>` dangling in an `ERROR` node. The contents of such `ERROR`
+/// nodes are replayed in order: a lone start tag opens an element unless it is void, an end tag
+/// closes the innermost open element with its name, and a void element that swallowed content hands
+/// it back to its siblings. Elements left open run to the end of `nodes`. Well-formed nodes pass
+/// through unchanged, and so do `ERROR` nodes with other causes, which builders skip.
+fn recover_children<'tree>(nodes: Vec<TsNode<'tree>>, source: &str) -> Vec<Child<'tree>> {
+    fn push<'tree>(
+        open: &mut [RecoveredElement<'tree>],
+        top: &mut Vec<Child<'tree>>,
+        child: Child<'tree>,
+    ) {
+        match open.last_mut() {
+            Some(element) => {
+                if let Child::Node(node) = &child {
+                    element.end = *node;
+                } else if let Child::Recovered(recovered) = &child {
+                    element.end = recovered.end;
+                }
+                element.children.push(child);
+            }
+            None => top.push(child),
+        }
+    }
+    let tag_type = |tag: TsNode<'_>| {
+        find_child(tag, "tag_name")
+            .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+            .map(NodeType::from_tag_name)
+    };
+
+    let mut top = Vec::new();
+    let mut open: Vec<RecoveredElement<'tree>> = Vec::new();
+    let mut pending: Vec<_> = nodes.into_iter().rev().collect();
+    while let Some(node) = pending.pop() {
         let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            if is_element(child) {
-                let child_id = build_ui_node(child, source, itree, Some(id));
-                itree.child_indices.push(child_id);
-            } else if is_text_node(child) {
-                if let Some(child_id) = build_text_node(child, source, itree, Some(id)) {
-                    itree.child_indices.push(child_id);
+        match node.kind() {
+            "ERROR" if has_swallowing_void(node, source) => pending.extend(
+                node.children(&mut cursor)
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev(),
+            ),
+            "start_tag" => {
+                let element = RecoveredElement {
+                    start_tag: node,
+                    children: Vec::new(),
+                    end: node,
+                };
+                if tag_type(node).is_some_and(|tag| tag.is_void()) {
+                    push(&mut open, &mut top, Child::Recovered(element));
+                } else {
+                    open.push(element);
+                }
+            }
+            "end_tag" => {
+                let tag = tag_type(node);
+                match open
+                    .iter()
+                    .rposition(|element| tag.is_some() && tag_type(element.start_tag) == tag)
+                {
+                    Some(index) => {
+                        open[index].end = node;
+                        while open.len() > index {
+                            let closed = open.pop().expect("index is below the length");
+                            push(&mut open, &mut top, Child::Recovered(closed));
+                        }
+                    }
+                    None => push(&mut open, &mut top, Child::Node(node)),
                 }
             }
+            "element" if swallowed_content(node, source) => {
+                let mut children: Vec<_> = node.children(&mut cursor).collect();
+                let start_tag = children.remove(0);
+                push(
+                    &mut open,
+                    &mut top,
+                    Child::Recovered(RecoveredElement {
+                        start_tag,
+                        children: Vec::new(),
+                        end: start_tag,
+                    }),
+                );
+                pending.extend(children.into_iter().rev());
+            }
+            _ => push(&mut open, &mut top, Child::Node(node)),
         }
     }
-    let child_end = itree.child_indices.len();
-    itree.nodes[id.index()].children = child_start..child_end;
-    id
+    while let Some(closed) = open.pop() {
+        push(&mut open, &mut top, Child::Recovered(closed));
+    }
+    top
+}
+
+/// Whether a void element below `error` swallowed content, see [`swallowed_content`].
+fn has_swallowing_void(error: TsNode<'_>, source: &str) -> bool {
+    let mut stack = vec![error];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "element" && swallowed_content(node, source) {
+            return true;
+        }
+        let mut cursor = node.walk();
+        stack.extend(
+            node.children(&mut cursor)
+                .filter(|child| matches!(child.kind(), "ERROR" | "element")),
+        );
+    }
+    false
+}
+
+/// Whether `element` opens with a void tag yet holds content or another element's end tag, as
+/// tree-sitter builds it for `<wbr>` written without a closing slash.
+fn swallowed_content(element: TsNode<'_>, source: &str) -> bool {
+    let mut cursor = element.walk();
+    let mut children = element.children(&mut cursor);
+    let Some(start_tag) = children.next().filter(|child| child.kind() == "start_tag") else {
+        return false;
+    };
+    let tag_name = |tag: TsNode<'_>| {
+        find_child(tag, "tag_name").and_then(|name| name.utf8_text(source.as_bytes()).ok())
+    };
+    let Some(name) = tag_name(start_tag) else {
+        return false;
+    };
+    NodeType::from_tag_name(name).is_void()
+        && children.any(|child| {
+            child.kind() != "end_tag"
+                || !tag_name(child).is_some_and(|end| end.eq_ignore_ascii_case(name))
+        })
 }
 
-fn build_bevy_tree<'source>(
-    id: NodeId,
-    nodes: &mut [Option<INode<'source>>],
-    child_indices: &[NodeId],
-) -> BevyNodeTree {
-    let inode = nodes[id.index()]
-        .take()
-        .expect("node id should exist once in the arena");
-    let children_range = inode.children.clone();
-    let children = child_indices[children_range]
+fn resolve_direction(
+    attributes: &Attributes<Cow<'_, str>>,
+    node: TsNode<'_>,
+    source: &str,
+    itree: &ITree<'_>,
+    parent: Option<NodeId>,
+) -> Direction {
+    attributes
+        .items
         .iter()
-        .map(|child_id| build_bevy_tree(*child_id, nodes, child_indices))
+        .find_map(|attribute| match attribute {
+            Attribute::Dir(value) if value.trim().eq_ignore_ascii_case("auto") => {
+                Some(first_strong_direction(node, source).unwrap_or_default())
+            }
+            Attribute::Dir(value) => Direction::from_dir_attribute(value),
+            _ => None,
+        })
+        .unwrap_or_else(|| inherited_direction(itree, parent))
+}
+
+/// Direction of the first strongly directional character in `node`'s text, in document order,
+/// for `dir="auto"`. Text that is never rendered, inside `<script>`, `<style>` and the like, is
+/// skipped.
+fn first_strong_direction(node: TsNode<'_>, source: &str) -> Option<Direction> {
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        if is_text_node(current) {
+            let text = current.utf8_text(source.as_bytes()).unwrap_or_default();
+            if let Some(direction) = decode_entities(text)
+                .chars()
+                .find_map(Direction::of_strong_char)
+            {
+                return Some(direction);
+            }
+            continue;
+        }
+        if current != node && is_element(current) {
+            let (info_node, _) = resolve_element_node(current);
+            let skipped = extract_tag_name(info_node, source).is_some_and(|tag| {
+                matches!(
+                    NodeType::from_tag_name(&tag),
+                    NodeType::Script | NodeType::Style | NodeType::Textarea
+                )
+            });
+            if skipped {
+                continue;
+            }
+        }
+        let mut cursor = current.walk();
+        let children: Vec<_> = current.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    None
+}
+
+fn inherited_direction(itree: &ITree<'_>, parent: Option<NodeId>) -> Direction {
+    parent
+        .map(|parent| itree.node(parent).direction)
+        .unwrap_or_default()
+}
+
+fn inherited_inert(itree: &ITree<'_>, parent: Option<NodeId>) -> bool {
+    parent.is_some_and(|parent| itree.node(parent).inert)
+}
+
+fn inherited_no_translate(itree: &ITree<'_>, parent: Option<NodeId>) -> bool {
+    parent.is_some_and(|parent| itree.node(parent).no_translate)
+}
+
+/// Cascades the rules of every `<style>` element, in document order, over the elements they match,
+/// below those elements' inline styles. Lengths in the rules are scaled and clamped like inline ones.
+fn apply_style_elements(itree: &mut ITree<'_>, settings: &ParseSettings) {
+    let sources: Vec<&str> = itree
+        .iter()
+        .filter(|(_, node, _)| node.node_type == NodeType::Style)
+        .filter_map(|(id, _, _)| {
+            let children = itree.children(id);
+            let first = itree.node(*children.first()?);
+            let last = itree.node(*children.last()?);
+            itree.source.get(first.start_byte..last.end_byte)
+        })
         .collect();
-    let text = inode
-        .text
-        .as_ref()
-        .map(|content| Text::new(content.as_ref()));
-    BevyNodeTree {
-        node: inode.to_bundle(),
-        text,
-        children,
+    if sources.is_empty() {
+        return;
+    }
+    let mut cx = StyleContext::new(settings.root_font_size);
+    let mut sheet = Stylesheet::default();
+    for source in sources {
+        sheet.rules.extend(Stylesheet::parse(source, &mut cx).rules);
+    }
+    for warning in &cx.warnings {
+        warn!("{}", warning);
+    }
+    let mut clamped = 0;
+    for rule in &mut sheet.rules {
+        if settings.px_scale != 1.0 {
+            rule.style.scale_px(settings.px_scale);
+        }
+        if let Some(max_px) = settings.max_px {
+            clamped += rule.style.clamp_px(max_px);
+        }
+    }
+    if clamped > 0 {
+        warn!(
+            "<style> rules have {} pixel length(s) beyond {}px, clamping",
+            clamped,
+            settings.max_px.unwrap_or_default()
+        );
+    }
+    for index in 0..itree.nodes.len() {
+        let id = NodeId::new(index);
+        let sheet_style = sheet.matching_style(itree, id, None, None);
+        let [hover_style, checked_style, disabled_style] = [
+            PseudoClass::Hover,
+            PseudoClass::Checked,
+            PseudoClass::Disabled,
+        ]
+        .map(|state| sheet.matching_style(itree, id, Some(state), None));
+        let [light_style, dark_style] = if sheet.has_color_scheme_rules(itree, id) {
+            [ColorScheme::Light, ColorScheme::Dark]
+                .map(|scheme| sheet.matching_style(itree, id, None, Some(scheme)))
+        } else {
+            [None, None]
+        };
+        if sheet_style.is_none()
+            && hover_style.is_none()
+            && checked_style.is_none()
+            && disabled_style.is_none()
+            && light_style.is_none()
+            && dark_style.is_none()
+        {
+            continue;
+        }
+        let inode = &mut itree.nodes[index];
+        let inline = inode
+            .attributes
+            .items
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Style(style) => Some(style.clone().into_owned()),
+                _ => None,
+            });
+        // Inline declarations outrank stylesheet rules, including those for a state.
+        let cascade = |style: StyleAttribute| match &inline {
+            Some(inline) => style.merge(inline),
+            None => style,
+        };
+        inode.cascaded_style = sheet_style.map(cascade);
+        inode.hover_style = hover_style.map(cascade);
+        inode.checked_style = checked_style.map(cascade);
+        inode.disabled_style = disabled_style.map(cascade);
+        inode.light_style = light_style.map(cascade);
+        inode.dark_style = dark_style.map(cascade);
+    }
+}
+
+/// Assigns ordinals to the `<li>` children of an `<ol>`. Unparsable `start`/`value` attributes are
+/// ignored so numbering simply continues, and counting stops at `i64::MAX` instead of overflowing.
+fn number_list_items(itree: &mut ITree<'_>, list_id: NodeId) {
+    let list = itree.node(list_id);
+    let mut ordinal = list
+        .attributes
+        .items
+        .iter()
+        .find_map(|attribute| match attribute {
+            Attribute::Start(value) => value.trim().parse::<i64>().ok(),
+            _ => None,
+        })
+        .unwrap_or(1);
+    let range = list.children.clone();
+    for position in range {
+        let item = &mut itree.nodes[itree.child_indices[position].index()];
+        if item.node_type != NodeType::Li {
+            continue;
+        }
+        if let Some(value) = item
+            .attributes
+            .items
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Value(value) => value.trim().parse::<i64>().ok(),
+                _ => None,
+            })
+        {
+            ordinal = value;
+        }
+        item.list_ordinal = Some(ordinal);
+        ordinal = ordinal.saturating_add(1);
     }
 }
 
 fn build_text_node<'tree, 'source>(
     node: TsNode<'tree>,
     source: &'source str,
+    settings: &ParseSettings,
     itree: &mut ITree<'source>,
     parent: Option<NodeId>,
 ) -> Option<NodeId> {
     let original_text = extract_text_slice(node, source);
-    let trimmed = original_text.trim();
-    if trimmed.is_empty() {
-        return None;
+    let content = if settings.preserve_raw_text {
+        if original_text.is_empty() {
+            return None;
+        }
+        Cow::Borrowed(original_text)
+    } else if inside_pre(itree, parent) {
+        if original_text.is_empty() {
+            return None;
+        }
+        expand_tabs(original_text, settings.tab_size)
+    } else {
+        let trimmed = original_text.trim_ascii();
+        if trimmed.is_empty() {
+            return None;
+        }
+        collapse_whitespace(trimmed)
+    };
+    // Decoded after collapsing so `&nbsp;` survives as a non-breaking space; raw text keeps its
+    // entities as written.
+    let content = match content {
+        content if settings.preserve_raw_text => content,
+        Cow::Borrowed(content) => decode_entities(content),
+        Cow::Owned(content) => Cow::Owned(decode_entities(&content).into_owned()),
+    };
+
+    Some(push_text_inode(
+        itree,
+        parent,
+        original_text,
+        content,
+        node.start_byte()..node.end_byte(),
+        (node.start_position(), node.end_position()),
+    ))
+}
+
+/// Ends the text run before a `<wbr>` with a zero-width space, the break opportunity it stands for,
+/// so `long<wbr>word` can wrap between the two halves.
+fn mark_word_break(itree: &mut ITree<'_>, previous: Option<NodeId>) {
+    let Some(previous) = previous else {
+        return;
+    };
+    if let Some(text) = &mut itree.nodes[previous.index()].text {
+        text.to_mut().push('\u{200B}');
     }
+}
 
-    let start = node.start_position();
-    let end = node.end_position();
+/// With `preserve_raw_text`, the whitespace tree-sitter skips between two children of an element
+/// is kept as a text node of its own.
+fn build_gap_text_node<'source>(
+    (previous_end, previous_point): (usize, Point),
+    (next_start, next_point): (usize, Point),
+    source: &'source str,
+    itree: &mut ITree<'source>,
+    parent: NodeId,
+) -> Option<NodeId> {
+    let bytes = previous_end..next_start;
+    let gap = source.get(bytes.clone()).filter(|gap| !gap.is_empty())?;
+    Some(push_text_inode(
+        itree,
+        Some(parent),
+        gap,
+        Cow::Borrowed(gap),
+        bytes,
+        (previous_point, next_point),
+    ))
+}
+
+fn push_text_inode<'source>(
+    itree: &mut ITree<'source>,
+    parent: Option<NodeId>,
+    original_text: &'source str,
+    content: Cow<'source, str>,
+    bytes: Range<usize>,
+    (start, end): (Point, Point),
+) -> NodeId {
     let id = NodeId::new(itree.nodes.len());
     itree.nodes.push(INode {
         id,
         node_type: NodeType::Text,
         attributes: Attributes::default(),
-        start_byte: node.start_byte(),
-        end_byte: node.end_byte(),
+        start_byte: bytes.start,
+        end_byte: bytes.end,
         start_position: TextPosition::new(start.column, start.row),
         end_position: TextPosition::new(end.column, end.row),
-        simplified_content: Cow::Borrowed(trimmed),
+        simplified_content: content.clone(),
         original_text,
-        text: Some(Cow::Borrowed(trimmed)),
+        text: Some(content),
         is_self_closing: true,
         parent,
         children: 0..0,
+        list_ordinal: None,
+        direction: inherited_direction(itree, parent),
+        inert: inherited_inert(itree, parent),
+        no_translate: inherited_no_translate(itree, parent),
+        tag_alias: None,
+        cascaded_style: None,
+        hover_style: None,
+        checked_style: None,
+        disabled_style: None,
+        light_style: None,
+        dark_style: None,
     });
+    id
+}
 
-    Some(id)
+fn count_error_nodes(root: TsNode<'_>) -> usize {
+    if !root.has_error() {
+        return 0;
+    }
+    let mut count = 0;
+    let mut cursor = root.walk();
+    loop {
+        let node = cursor.node();
+        if node.is_error() || node.is_missing() {
+            count += 1;
+        }
+        // Subtrees without errors are skipped entirely.
+        if node.has_error() && cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return count;
+            }
+        }
+    }
+}
+
+fn inside_pre(itree: &ITree<'_>, mut current: Option<NodeId>) -> bool {
+    while let Some(id) = current {
+        let node = itree.node(id);
+        if node.node_type == NodeType::Pre {
+            return true;
+        }
+        current = node.parent;
+    }
+    false
+}
+
+/// Folds every run of ASCII whitespace, tabs and newlines included, into a single space, edges
+/// included; like in HTML, non-breaking spaces are left alone.
+fn collapse_whitespace(text: &str) -> Cow<'_, str> {
+    let mut chars = text.chars().peekable();
+    let mut needs_work = false;
+    while let Some(ch) = chars.next() {
+        if ch.is_ascii_whitespace()
+            && (ch != ' ' || chars.peek().is_some_and(|next| next.is_ascii_whitespace()))
+        {
+            needs_work = true;
+            break;
+        }
+    }
+    if !needs_work {
+        return Cow::Borrowed(text);
+    }
+    let words = text.split_ascii_whitespace().collect::<Vec<_>>().join(" ");
+    if words.is_empty() {
+        return Cow::Borrowed(" ");
+    }
+    let mut collapsed = String::with_capacity(words.len() + 2);
+    if text.starts_with(|ch: char| ch.is_ascii_whitespace()) {
+        collapsed.push(' ');
+    }
+    collapsed.push_str(&words);
+    if text.ends_with(|ch: char| ch.is_ascii_whitespace()) {
+        collapsed.push(' ');
+    }
+    Cow::Owned(collapsed)
+}
+
+/// Replaces tabs with spaces up to the next multiple of `tab_size`, counting columns per line.
+fn expand_tabs(text: &str, tab_size: usize) -> Cow<'_, str> {
+    if !text.contains('\t') {
+        return Cow::Borrowed(text);
+    }
+    let mut expanded = String::with_capacity(text.len());
+    let mut column = 0;
+    for ch in text.chars() {
+        match ch {
+            '\t' if tab_size > 0 => {
+                let width = tab_size - column % tab_size;
+                expanded.extend(std::iter::repeat_n(' ', width));
+                column += width;
+            }
+            '\t' => {}
+            '\n' => {
+                expanded.push(ch);
+                column = 0;
+            }
+            _ => {
+                expanded.push(ch);
+                column += 1;
+            }
+        }
+    }
+    Cow::Owned(expanded)
 }
 
 fn extract_tag_name<'tree>(node: TsNode<'tree>, source: &str) -> Option<String> {
@@ -266,6 +1454,12 @@ fn extract_tag_name<'tree>(node: TsNode<'tree>, source: &str) -> Option<String>
         return Some(tag_text.to_string());
     }
 
+    if node.kind() == "start_tag" {
+        let tag_node = find_child(node, "tag_name")?;
+        let tag_text = tag_node.utf8_text(source.as_bytes()).ok()?;
+        return Some(tag_text.to_string());
+    }
+
     if node.kind() == "element" {
         let start_tag = find_child(node, "start_tag")?;
         let tag_node = find_child(start_tag, "tag_name")?;
@@ -285,15 +1479,112 @@ fn find_child<'tree>(node: TsNode<'tree>, kind: &str) -> Option<TsNode<'tree>> {
 fn collect_root_elements<'tree, 'source>(
     node: TsNode<'tree>,
     source: &'source str,
+    settings: &ParseSettings,
     itree: &mut ITree<'source>,
 ) -> Vec<NodeId> {
     let mut cursor = node.walk();
-    node.children(&mut cursor)
-        .filter(|child| is_element(*child))
-        .map(|child| build_ui_node(child, source, itree, None))
+    let children = node.children(&mut cursor).collect();
+    recover_children(children, source)
+        .into_iter()
+        .filter(|child| !matches!(child, Child::Node(node) if !is_element(*node)))
+        .filter_map(|child| build_child(child, source, settings, itree, None))
         .collect()
 }
 
+/// Wraps the top-level text of an element-less document in an attribute-less `<div>` spanning the
+/// whole input. Returns `None` when the document holds no text either.
+fn build_implicit_root<'tree, 'source>(
+    document: TsNode<'tree>,
+    source: &'source str,
+    settings: &ParseSettings,
+    itree: &mut ITree<'source>,
+) -> Option<NodeId> {
+    let mut cursor = document.walk();
+    let text_nodes: Vec<_> = document
+        .children(&mut cursor)
+        .filter(|child| is_text_node(*child))
+        .collect();
+    if text_nodes.is_empty() {
+        return None;
+    }
+    let start = document.start_position();
+    let end = document.end_position();
+    let original_text = extract_text_slice(document, source);
+    let id = NodeId::new(itree.nodes.len());
+    itree.nodes.push(INode {
+        id,
+        node_type: NodeType::Div,
+        attributes: Attributes::default(),
+        start_byte: document.start_byte(),
+        end_byte: document.end_byte(),
+        start_position: TextPosition::new(start.column, start.row),
+        end_position: TextPosition::new(end.column, end.row),
+        simplified_content: Cow::Borrowed(original_text),
+        original_text,
+        is_self_closing: false,
+        parent: None,
+        children: 0..0,
+        text: None,
+        list_ordinal: None,
+        direction: Direction::default(),
+        inert: false,
+        no_translate: false,
+        tag_alias: None,
+        cascaded_style: None,
+        hover_style: None,
+        checked_style: None,
+        disabled_style: None,
+        light_style: None,
+        dark_style: None,
+    });
+    let child_ids: Vec<_> = text_nodes
+        .into_iter()
+        .filter_map(|child| build_text_node(child, source, settings, itree, Some(id)))
+        .collect();
+    let child_start = itree.child_indices.len();
+    itree.child_indices.extend(child_ids);
+    let child_end = itree.child_indices.len();
+    itree.nodes[id.index()].children = child_start..child_end;
+    Some(id)
+}
+
+/// Spells out the CSS initial `align-items: stretch` and `justify-content: flex-start` on flex
+/// containers that left them at Bevy's `Default`, when `enabled` by
+/// [`ParseSettings::css_flex_alignment`].
+fn apply_css_flex_alignment(node: &mut Node, enabled: bool) {
+    if !enabled || node.display != Display::Flex {
+        return;
+    }
+    if node.align_items == AlignItems::Default {
+        node.align_items = AlignItems::Stretch;
+    }
+    if node.justify_content == JustifyContent::Default {
+        node.justify_content = JustifyContent::FlexStart;
+    }
+}
+
+/// Tags every child of a `<details>` but its first `<summary>` as disclosure body, hiding them
+/// while it is closed.
+fn mark_disclosure_body(children: &mut [BevyNodeTree], open: bool) {
+    let summary = children
+        .iter()
+        .position(|child| child.node.node_kind.kind == NodeType::Summary);
+    for (index, child) in children.iter_mut().enumerate() {
+        if Some(index) == summary {
+            continue;
+        }
+        child.disclosure_body = Some(DisclosureBody(child.node.node.display));
+        if !open {
+            child.node.node.display = Display::None;
+        }
+    }
+}
+
+/// URL of the first image candidate in a `srcset`, dropping its width or density descriptor.
+fn first_srcset_url(srcset: &str) -> Option<&str> {
+    srcset.split(',').next()?.split_ascii_whitespace().next()
+}
+
 fn is_element<'tree>(node: TsNode<'tree>) -> bool {
     matches!(node.kind(), "element" | "self_closing_element")
 }
@@ -381,10 +1672,11 @@ fn resolve_element_node<'tree>(node: TsNode<'tree>) -> (TsNode<'tree>, bool) {
     if node.kind() == "element" {
         let has_start_tag = find_child(node, "start_tag").is_some();
         let has_end_tag = find_child(node, "end_tag").is_some();
-        if !has_start_tag && !has_end_tag {
-            if let Some(self_closing) = find_child(node, "self_closing_element") {
-                return (self_closing, true);
-            }
+        if !has_start_tag
+            && !has_end_tag
+            && let Some(self_closing) = find_child(node, "self_closing_element")
+        {
+            return (self_closing, true);
         }
     }
 
@@ -394,10 +1686,11 @@ fn resolve_element_node<'tree>(node: TsNode<'tree>) -> (TsNode<'tree>, bool) {
 fn extract_attributes<'tree, 'source>(
     node: TsNode<'tree>,
     source: &'source str,
+    settings: &ParseSettings,
 ) -> Attributes<Cow<'source, str>> {
     let mut attributes = Attributes::default();
     let attribute_parent = match node.kind() {
-        "self_closing_element" => Some(node),
+        "self_closing_element" | "start_tag" => Some(node),
         "element" => find_child(node, "start_tag"),
         _ => None,
     };
@@ -406,13 +1699,50 @@ fn extract_attributes<'tree, 'source>(
         return attributes;
     };
 
+    let options = AttributeOptions {
+        strict_bool: settings.strict_bool,
+        root_font_px: settings.root_font_size,
+    };
     let mut cursor = parent.walk();
+    let mut kept = 0;
     for child in parent.children(&mut cursor) {
         if child.kind() != "attribute" {
             continue;
         }
-        if let Some((name, value)) = parse_attribute(child, source) {
-            attributes.add_raw_attribute(name, value);
+        let Some((name, value)) = parse_attribute(child, source) else {
+            continue;
+        };
+        if !settings.attribute_filter.permits(&name) {
+            debug!("dropping filtered attribute '{}'", name);
+            continue;
+        }
+        if !settings.event_bindings && event_name(&name).is_some() {
+            debug!("dropping inline event handler '{}'", name);
+            continue;
+        }
+        if kept == settings.max_attributes {
+            warn!(
+                "element at byte {} exceeds {} attributes, ignoring the rest",
+                node.start_byte(),
+                settings.max_attributes
+            );
+            break;
+        }
+        attributes.add_raw_attribute_with(name, value, options);
+        kept += 1;
+    }
+    if settings.px_scale != 1.0 {
+        attributes.scale_px(settings.px_scale);
+    }
+    if let Some(max_px) = settings.max_px {
+        let clamped = attributes.clamp_px(max_px);
+        if clamped > 0 {
+            warn!(
+                "element at byte {} has {} pixel length(s) beyond {}px, clamping",
+                node.start_byte(),
+                clamped,
+                max_px
+            );
         }
     }
 
@@ -436,7 +1766,10 @@ fn extract_attribute_value<'tree, 'source>(
     source: &'source str,
 ) -> Option<Cow<'source, str>> {
     let raw_value = node.utf8_text(source.as_bytes()).ok()?;
-    Some(unquote_attribute_value(raw_value))
+    Some(match unquote_attribute_value(raw_value) {
+        Cow::Borrowed(value) => decode_entities(value),
+        Cow::Owned(value) => Cow::Owned(decode_entities(&value).into_owned()),
+    })
 }
 
 fn unquote_attribute_value<'source>(value: &'source str) -> Cow<'source, str> {
@@ -450,3 +1783,519 @@ fn unquote_attribute_value<'source>(value: &'source str) -> Cow<'source, str> {
     }
     Cow::Borrowed(value)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        attributes::EventBindings,
+        settings::{AttributeFilter, DEFAULT_MAX_PX},
+        BevymlParser,
+    };
+
+    fn parse_with<'source>(source: &'source str, settings: ParseSettings) -> ITree<'source> {
+        BevymlParser::new()
+            .with_settings(settings)
+            .parse(source)
+            .unwrap()
+    }
+
+    #[test]
+    fn child_nodes_yields_typed_children_in_order() {
+        let source = "<div><p>one</p><span>two</span><img /></div>";
+        let itree = BevymlParser::new().parse(source).unwrap();
+        let types: Vec<_> = itree
+            .child_nodes(itree.roots[0])
+            .map(|child| child.node_type.clone())
+            .collect();
+        assert_eq!(types, [NodeType::P, NodeType::Span, NodeType::Img]);
+    }
+
+    /// Collects ids the way [`ITree::pretty_print`] visits them.
+    fn print_order(
+        itree: &ITree<'_>,
+        nodes: &[NodeId],
+        out: &mut Vec<(NodeId, usize)>,
+        depth: usize,
+    ) {
+        for node_id in nodes {
+            out.push((*node_id, depth));
+            print_order(itree, itree.children(*node_id), out, depth + 1);
+        }
+    }
+
+    #[test]
+    fn iter_matches_pretty_print_order_and_visits_every_node() {
+        let source = "<div><ul><li>a</li><li>b <b>c</b></li></ul><p>d</p></div>";
+        let itree = BevymlParser::new().parse(source).unwrap();
+        let mut expected = Vec::new();
+        print_order(&itree, &itree.roots, &mut expected, 0);
+        let visited: Vec<_> = itree.iter().map(|(id, _, depth)| (id, depth)).collect();
+        assert_eq!(visited, expected);
+        assert_eq!(visited.len(), itree.nodes.len());
+    }
+
+    #[test]
+    fn denylisted_onclick_is_dropped_while_others_survive() {
+        let settings = ParseSettings {
+            attribute_filter: AttributeFilter::Deny(vec!["onclick".to_string()]),
+            event_bindings: true,
+            ..Default::default()
+        };
+        let itree = parse_with(
+            r#"<button id="save" onclick="save" onfocus="track">Save</button>"#,
+            settings,
+        );
+        let attributes = &itree.node(itree.roots[0]).attributes;
+        let custom_names: Vec<_> = attributes
+            .items
+            .iter()
+            .filter_map(|attribute| match attribute {
+                Attribute::Custom { name, .. } => Some(name.as_ref()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(custom_names, ["onfocus"]);
+        assert_eq!(attributes.id(), Some("save"));
+    }
+
+    #[test]
+    fn scripts_are_dropped_unless_allowed() {
+        let source = "<div><script>run()</script><p>kept</p></div>";
+        let has_script = |itree: &ITree<'_>| {
+            itree
+                .nodes
+                .iter()
+                .any(|node| node.node_type == NodeType::Script)
+        };
+        assert!(!has_script(&parse_with(source, ParseSettings::default())));
+        let trusted = ParseSettings {
+            allow_scripts: true,
+            ..Default::default()
+        };
+        assert!(has_script(&parse_with(source, trusted)));
+    }
+
+    #[test]
+    fn noscript_is_kept_unless_scripting_is_enabled() {
+        let source = "<div><noscript><p>fallback</p></noscript><p>kept</p></div>";
+        let has_fallback = |itree: &ITree<'_>| {
+            itree
+                .nodes
+                .iter()
+                .any(|node| node.node_type == NodeType::NoScript)
+                && itree
+                    .nodes
+                    .iter()
+                    .any(|node| node.original_text == "fallback")
+        };
+        assert!(has_fallback(&parse_with(source, ParseSettings::default())));
+        let scripting = ParseSettings {
+            scripting_enabled: true,
+            ..Default::default()
+        };
+        let itree = parse_with(source, scripting);
+        assert!(!has_fallback(&itree));
+        assert!(itree.nodes.iter().any(|node| node.original_text == "kept"));
+    }
+
+    #[test]
+    fn attributes_past_the_cap_are_ignored() {
+        let settings = ParseSettings {
+            max_attributes: 2,
+            ..Default::default()
+        };
+        let itree = parse_with(
+            r#"<div id="a" title="b" lang="c" role="d"></div>"#,
+            settings,
+        );
+        let attributes = &itree.node(itree.roots[0]).attributes;
+        assert_eq!(attributes.items.len(), 2);
+        assert_eq!(attributes.id(), Some("a"));
+    }
+
+    #[test]
+    fn ordered_lists_honor_start_and_value() {
+        let itree = BevymlParser::new()
+            .parse(r#"<ol start="3"><li/><li value="10"/><li/></ol>"#)
+            .unwrap();
+        let ordinals: Vec<_> = itree
+            .child_nodes(itree.roots[0])
+            .map(|item| item.list_ordinal)
+            .collect();
+        assert_eq!(ordinals, [Some(3), Some(10), Some(11)]);
+    }
+
+    #[test]
+    fn ordered_list_numbering_saturates_at_i64_max() {
+        let ordinals = |source: &str| {
+            let itree = BevymlParser::new().parse(source).unwrap();
+            itree
+                .child_nodes(itree.roots[0])
+                .map(|item| item.list_ordinal)
+                .collect::<Vec<_>>()
+        };
+        let max = i64::MAX;
+        assert_eq!(
+            ordinals(&format!(r#"<ol start="{max}"><li>a</li><li>b</li></ol>"#)),
+            [Some(max), Some(max)]
+        );
+        assert_eq!(
+            ordinals(&format!(r#"<ol><li value="{max}">a</li><li>b</li></ol>"#)),
+            [Some(max), Some(max)]
+        );
+    }
+
+    #[test]
+    fn rtl_lists_move_the_marker_gutter_to_the_right() {
+        let padding = |source| {
+            let itree = BevymlParser::new().parse(source).unwrap();
+            itree.node(itree.roots[0]).to_bundle().node.padding
+        };
+        let ltr = padding("<ul><li>a</li></ul>");
+        let rtl = padding(r#"<ul dir="rtl"><li>a</li></ul>"#);
+        assert_ne!(ltr, rtl);
+        assert_eq!((rtl.left, rtl.right), (ltr.right, ltr.left));
+    }
+
+    #[test]
+    fn to_bevy_trees_can_run_twice_on_the_same_tree() {
+        let itree = BevymlParser::new()
+            .parse("<div><p>hello</p><button>ok</button></div>")
+            .unwrap();
+        let first = itree.to_bevy_trees();
+        let second = itree.to_bevy_trees();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].canonical_debug(), second[0].canonical_debug());
+        assert_eq!(itree.child_nodes(itree.roots[0]).count(), 2);
+    }
+
+    #[test]
+    fn px_scale_multiplies_pixel_lengths_only() {
+        let settings = ParseSettings {
+            px_scale: 2.0,
+            ..Default::default()
+        };
+        let itree = parse_with(r#"<div style="width: 10px; height: 50%"></div>"#, settings);
+        let node = itree.node(itree.roots[0]).to_bundle().node;
+        assert_eq!(node.width, Val::Px(20.0));
+        assert_eq!(node.height, Val::Percent(50.0));
+    }
+
+    #[test]
+    fn line_clamp_becomes_max_lines() {
+        let itree = BevymlParser::new()
+            .parse(r#"<p style="line-clamp: 2">long text</p>"#)
+            .unwrap();
+        let trees = itree.to_bevy_trees();
+        // The clamp lands on the text node, which is what Bevy lays out.
+        assert_eq!(trees[0].children[0].max_lines, Some(MaxLines(2)));
+    }
+
+    #[test]
+    fn source_text_is_the_nodes_slice_of_the_input() {
+        let source = r#"<div>
+  <section><p class="x">hi <b>there</b></p></section>
+</div>"#;
+        let itree = BevymlParser::new().parse(source).unwrap();
+        let (id, node, _) = itree
+            .iter()
+            .find(|(_, node, _)| node.node_type == NodeType::P)
+            .unwrap();
+        let expected = r#"<p class="x">hi <b>there</b></p>"#;
+        assert_eq!(itree.source_text(id), expected);
+        assert_eq!(&source[node.start_byte..node.end_byte], expected);
+    }
+
+    #[test]
+    fn oversized_pixel_lengths_are_clamped() {
+        let settings = ParseSettings {
+            max_px: Some(DEFAULT_MAX_PX),
+            ..Default::default()
+        };
+        let itree = parse_with(r#"<div style="width: 999999999px"></div>"#, settings);
+        let node = itree.node(itree.roots[0]).to_bundle().node;
+        assert_eq!(node.width, Val::Px(DEFAULT_MAX_PX));
+
+        // The count is what the warning is logged for.
+        let mut attributes = Attributes::<String>::default();
+        attributes.add_raw_attribute("style", Some("width: -999999999px".to_string()));
+        assert_eq!(attributes.clamp_px(DEFAULT_MAX_PX), 1);
+    }
+
+    #[test]
+    fn pre_tabs_expand_to_tab_size() {
+        let settings = ParseSettings {
+            tab_size: 2,
+            ..Default::default()
+        };
+        let itree = parse_with("<pre>\tabc</pre>", settings);
+        let text = itree.to_bevy_trees()[0].children[0].text.clone().unwrap();
+        assert_eq!(text.0, "  abc");
+    }
+
+    #[test]
+    fn error_node_count_reports_unclosed_tags() {
+        let clean = BevymlParser::new().parse("<div><p>a</p></div>").unwrap();
+        assert_eq!(clean.error_node_count, 0);
+        let unclosed = BevymlParser::new()
+            .parse("<div><p>a</p></div><section>")
+            .unwrap();
+        assert!(unclosed.error_node_count > 0);
+    }
+
+    #[test]
+    fn unclosed_tags_add_no_duplicate_or_partial_nodes() {
+        let itree = BevymlParser::new()
+            .parse("<div><p>a</p></div><section>")
+            .unwrap();
+        let types: Vec<_> = itree
+            .iter()
+            .map(|(_, node, _)| node.node_type.clone())
+            .collect();
+        assert_eq!(types, [NodeType::Div, NodeType::P, NodeType::Text]);
+        assert_eq!(itree.roots.len(), 1);
+    }
+
+    #[test]
+    fn preserve_raw_text_keeps_whitespace_only_text() {
+        let source = "<div>\n  <p> a &amp; b </p>\n</div>";
+        let texts = |itree: &ITree<'_>| {
+            itree
+                .nodes
+                .iter()
+                .filter(|node| node.node_type == NodeType::Text)
+                .map(|node| node.text.as_deref().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            texts(&parse_with(source, ParseSettings::default())),
+            ["a", "&", "b"]
+        );
+        let settings = ParseSettings {
+            preserve_raw_text: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            texts(&parse_with(source, settings)),
+            ["\n  ", " a ", "&amp;", " b ", "\n"]
+        );
+    }
+
+    #[test]
+    fn position_of_counts_rows_and_columns() {
+        let source = "<div>\n  <p>hi</p>\n</div>";
+        let itree = BevymlParser::new().parse(source).unwrap();
+        assert_eq!(itree.position_of(0), TextPosition::new(0, 0));
+        assert_eq!(itree.position_of(3), TextPosition::new(3, 0));
+        // Byte 6 is the first one after the newline.
+        assert_eq!(itree.position_of(6), TextPosition::new(0, 1));
+        assert_eq!(itree.position_of(8), TextPosition::new(2, 1));
+        let p = itree.child_nodes(itree.roots[0]).next().unwrap();
+        assert_eq!(itree.position_of(p.start_byte), p.start_position);
+    }
+
+    #[test]
+    fn text_only_documents_need_allow_text_root() {
+        let source = "just some text";
+        assert!(matches!(
+            BevymlParser::new().parse(source),
+            Err(ITreeError::MissingRootElement)
+        ));
+        let settings = ParseSettings {
+            allow_text_root: true,
+            ..Default::default()
+        };
+        let itree = parse_with(source, settings);
+        assert_eq!(itree.roots.len(), 1);
+        let root = itree.node(itree.roots[0]);
+        assert_eq!(root.node_type, NodeType::Div);
+        assert_eq!(
+            itree.child_nodes(root.id).next().unwrap().text.as_deref(),
+            Some("just some text")
+        );
+    }
+
+    #[test]
+    fn verbose_pretty_string_lists_attributes_and_unsupported_styles() {
+        let itree = BevymlParser::new()
+            .parse(r#"<p id="intro" style="width: 10px; float: left">Hi</p>"#)
+            .unwrap();
+        let expected = r#"- node_type=P element=p simplified_content="<p id=\"intro\" style=\"width: 10px; float: left\">Hi</p>"
+    attribute Id("intro")
+    attribute Style(StyleAttribute { raw: "width: 10px; float: left", declarations: [Width(Px(10.0))], unsupported: [UnsupportedStyle { property: "float", value: "left" }], important: [] })
+    unsupported style float: left
+  - node_type=Text element=#text simplified_content="Hi"
+"#;
+        assert_eq!(itree.pretty_string_verbose(), expected);
+    }
+
+    #[cfg(feature = "debug-names")]
+    #[test]
+    fn sibling_list_items_get_distinct_indexed_names() {
+        let trees = BevymlParser::new()
+            .parse("<ul><li>a</li><li>b</li></ul>")
+            .unwrap()
+            .to_bevy_trees();
+        let names: Vec<&str> = trees[0]
+            .children
+            .iter()
+            .map(|child| child.node.name.as_str())
+            .collect();
+        assert_eq!(names, ["ul[1] > li[1]", "ul[1] > li[2]"]);
+    }
+
+    #[test]
+    fn picture_images_prefer_the_first_source() {
+        let image_source = |source: &str| {
+            let trees = BevymlParser::new().parse(source).unwrap().to_bevy_trees();
+            trees[0]
+                .children
+                .iter()
+                .find(|child| child.node.node_kind.kind == NodeType::Img)
+                .unwrap()
+                .image_source
+                .clone()
+        };
+        assert_eq!(
+            image_source(
+                r#"<picture><source srcset="wide.png 2x, small.png" /><img src="fallback.png" /></picture>"#
+            ),
+            Some(ImageSource("wide.png".to_string()))
+        );
+        assert_eq!(
+            image_source(r#"<picture><img src="fallback.png" /></picture>"#),
+            Some(ImageSource("fallback.png".to_string()))
+        );
+    }
+
+    #[test]
+    fn onclick_becomes_an_event_binding_only_when_enabled() {
+        let source = r#"<button onclick="save">Save</button>"#;
+        let bindings = |settings| {
+            let itree = parse_with(source, settings);
+            EventBindings::from_attributes(&itree.node(itree.roots[0]).attributes)
+        };
+        let enabled = ParseSettings {
+            event_bindings: true,
+            ..Default::default()
+        };
+        assert_eq!(bindings(enabled).unwrap().get("click"), Some("save"));
+        assert!(bindings(ParseSettings::default()).is_none());
+    }
+
+    #[test]
+    fn strike_resolves_to_the_strikethrough_element() {
+        let itree = BevymlParser::new()
+            .parse("<div><strike>old</strike><center>mid</center></div>")
+            .unwrap();
+        let children: Vec<_> = itree.child_nodes(itree.roots[0]).collect();
+        assert_eq!(children[0].node_type, NodeType::S);
+        assert_eq!(children[0].tag_alias.as_deref(), Some("strike"));
+        assert_eq!(children[1].node_type, NodeType::Div);
+        assert_eq!(children[1].to_bundle().node.align_items, AlignItems::Center);
+    }
+
+    #[test]
+    fn computed_node_applies_inline_width_and_padding() {
+        let itree = BevymlParser::new()
+            .parse(r#"<div style="width:50px;padding:4px">x</div>"#)
+            .unwrap();
+        let node = itree.computed_node(itree.roots[0], &ParseSettings::default());
+        assert_eq!(node.width, Val::Px(50.0));
+        assert_eq!(node.padding, bevy_ui::UiRect::all(Val::Px(4.0)));
+        let flex = BevymlParser::new()
+            .parse(r#"<div style="display:flex">x</div>"#)
+            .unwrap();
+        let css = ParseSettings {
+            css_flex_alignment: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            flex.computed_node(flex.roots[0], &ParseSettings::default())
+                .align_items,
+            AlignItems::Default
+        );
+        assert_eq!(
+            flex.computed_node(flex.roots[0], &css).align_items,
+            AlignItems::Stretch
+        );
+    }
+
+    #[test]
+    fn dir_auto_follows_the_first_strong_character() {
+        let direction = |source: &str| {
+            let itree = BevymlParser::new().parse(source).unwrap();
+            itree.node(itree.roots[0]).direction
+        };
+        assert_eq!(
+            direction(r#"<p dir="auto">مرحبا hello</p>"#),
+            Direction::Rtl
+        );
+        assert_eq!(
+            direction(r#"<p dir="auto">hello مرحبا</p>"#),
+            Direction::Ltr
+        );
+        assert_eq!(direction(r#"<p dir="auto">123</p>"#), Direction::Ltr);
+    }
+
+    #[test]
+    fn css_flex_alignment_fills_in_css_initial_values() {
+        let settings = ParseSettings {
+            css_flex_alignment: true,
+            ..Default::default()
+        };
+        let trees = parse_with(
+            r#"<div style="display:flex"><div style="display:flex; align-items:center">x</div></div>"#,
+            settings,
+        )
+        .to_bevy_trees();
+        let outer = &trees[0].node.node;
+        assert_eq!(outer.align_items, AlignItems::Stretch);
+        assert_eq!(outer.justify_content, JustifyContent::FlexStart);
+        let inner = &trees[0].children[0].node.node;
+        assert_eq!(inner.align_items, AlignItems::Center);
+    }
+
+    #[test]
+    fn wbr_becomes_a_zero_width_space() {
+        for source in ["<p>long<wbr>word</p>", "<p>long<wbr />word</p>"] {
+            let trees = BevymlParser::new().parse(source).unwrap().to_bevy_trees();
+            let text: String = trees[0]
+                .text_runs()
+                .iter()
+                .map(|run| run.text.as_str())
+                .collect();
+            assert_eq!(text, "long\u{200B}word", "{source}");
+        }
+    }
+
+    #[test]
+    fn unclosed_void_tags_do_not_swallow_their_siblings() {
+        let shape = |source| {
+            let itree = BevymlParser::new().parse(source).unwrap();
+            itree
+                .iter()
+                .map(|(_, node, depth)| {
+                    let name = match &node.text {
+                        Some(text) => format!("{text:?}"),
+                        None => node.node_type.tag_name().into_owned(),
+                    };
+                    format!("{}{name}", "  ".repeat(depth))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert_eq!(
+            shape(r#"<div><p>a<br>b</p><img src="x.png"><p>c</p></div>"#),
+            "div\n  p\n    \"a\"\n    br\n    \"b\"\n  img\n  p\n    \"c\""
+        );
+        assert_eq!(shape("<p>a<wbr></p>"), "p\n  \"a\\u{200b}\"\n  wbr");
+        let itree = BevymlParser::new()
+            .parse(r#"<div><input type="text" name="q"></div>"#)
+            .unwrap();
+        let input = itree.child_nodes(itree.roots[0]).next().unwrap();
+        assert_eq!(input.node_type, NodeType::Input);
+        assert_eq!(input.attributes.items.len(), 2);
+    }
+}
@@ -1,26 +1,115 @@
+#[cfg(feature = "bevy")]
 pub use tree_sitter;
 
+#[cfg(feature = "bevy")]
 pub mod attributes;
+pub mod entities;
+#[cfg(feature = "bevy")]
 pub mod inode;
+#[cfg(feature = "bevy")]
 pub mod itree;
+#[cfg(feature = "bevy")]
+mod named_colors;
+pub mod raw;
+#[cfg(feature = "bevy")]
+pub mod settings;
+#[cfg(feature = "bevy")]
+pub mod stylesheet;
+#[cfg(feature = "bevy")]
 use tree_sitter::Tree;
 
+#[cfg(feature = "bevy")]
 use bevy_derive::{Deref, DerefMut};
-use std::{fs as blocking_fs, io, path::Path};
+#[cfg(feature = "bevy")]
+use std::{fmt, fs as blocking_fs, io, path::Path};
+#[cfg(feature = "bevy")]
 use tokio::fs as tokio_fs;
-use tree_sitter::{LanguageError, Parser};
+#[cfg(feature = "bevy")]
+use tree_sitter::{
+    Language, LanguageError, Parser, LANGUAGE_VERSION, MIN_COMPATIBLE_LANGUAGE_VERSION,
+};
 
-use crate::itree::{ITree, ITreeError};
+#[cfg(feature = "bevy")]
+use crate::{
+    itree::{ITree, ITreeError},
+    settings::ParseSettings,
+};
 
+/// Why the Bevyml grammar could not be loaded into a tree-sitter parser.
+#[cfg(feature = "bevy")]
+#[derive(Debug)]
+pub enum GrammarError {
+    /// The generated grammar targets an ABI the linked `tree-sitter` crate cannot load, which
+    /// usually means one of the two was upgraded without the other.
+    AbiMismatch {
+        grammar: usize,
+        min: usize,
+        max: usize,
+    },
+    Language(LanguageError),
+}
+
+#[cfg(feature = "bevy")]
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrammarError::AbiMismatch { grammar, min, max } => write!(
+                f,
+                "bevyml grammar uses tree-sitter ABI {grammar}, but the linked tree-sitter crate \
+                 supports ABI {min} to {max}; regenerate tree-sitter-bevyml or align the \
+                 tree-sitter version"
+            ),
+            GrammarError::Language(err) => write!(f, "could not load bevyml grammar: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl std::error::Error for GrammarError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GrammarError::AbiMismatch { .. } => None,
+            GrammarError::Language(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "bevy")]
 #[derive(Deref, DerefMut)]
-pub struct BevymlParser(Parser);
+pub struct BevymlParser {
+    #[deref]
+    parser: Parser,
+    settings: ParseSettings,
+}
+
+#[cfg(feature = "bevy")]
+impl Default for BevymlParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
+#[cfg(feature = "bevy")]
 impl BevymlParser {
     /// Attempts to create a parser bound to the Bevyml language so the caller can deal with any
-    /// `LanguageError` that shows up.
-    pub fn try_new() -> Result<Self, LanguageError> {
-        let mut parser = Self(Parser::new());
-        parser.set_language(&tree_sitter_bevyml::LANGUAGE.into())?;
+    /// [`GrammarError`] that shows up.
+    pub fn try_new() -> Result<Self, GrammarError> {
+        let mut parser = Self {
+            parser: Parser::new(),
+            settings: ParseSettings::default(),
+        };
+        let language: Language = tree_sitter_bevyml::LANGUAGE.into();
+        let grammar = language.abi_version();
+        if !(MIN_COMPATIBLE_LANGUAGE_VERSION..=LANGUAGE_VERSION).contains(&grammar) {
+            return Err(GrammarError::AbiMismatch {
+                grammar,
+                min: MIN_COMPATIBLE_LANGUAGE_VERSION,
+                max: LANGUAGE_VERSION,
+            });
+        }
+        parser
+            .set_language(&language)
+            .map_err(GrammarError::Language)?;
 
         Ok(parser)
     }
@@ -30,12 +119,26 @@ impl BevymlParser {
         Self::try_new().expect("Error loading Bevyml grammar.")
     }
 
+    /// Replaces the settings used by every subsequent [`BevymlParser::parse`] call.
+    pub fn with_settings(mut self, settings: ParseSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    pub fn settings(&self) -> &ParseSettings {
+        &self.settings
+    }
+
+    pub fn settings_mut(&mut self) -> &mut ParseSettings {
+        &mut self.settings
+    }
+
     pub fn parse<'source>(&mut self, txt: &'source str) -> Result<ITree<'source>, ITreeError> {
         let tree = self
-            .0
+            .parser
             .parse(txt, None)
             .ok_or(ITreeError::MissingParseTree)?;
-        ITree::try_from((&tree, txt))
+        ITree::try_from((&tree, txt, &self.settings))
     }
 
     /// Parses the contents of a file asynchronously using Tokio-backed file I/O.
@@ -44,7 +147,7 @@ impl BevymlParser {
         P: AsRef<Path>,
     {
         let source = tokio_fs::read_to_string(path.as_ref()).await?;
-        Ok(self.0.parse(&source, None))
+        Ok(self.parser.parse(&source, None))
     }
 
     /// Parses the contents of a file with blocking I/O via Pollster so callers that do not run on an
@@ -57,7 +160,31 @@ impl BevymlParser {
 
         pollster::block_on(async {
             let source = blocking_fs::read_to_string(&path)?;
-            Ok(self.0.parse(&source, None))
+            Ok(self.parser.parse(&source, None))
         })
     }
 }
+
+#[cfg(all(test, feature = "bevy"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abi_mismatch_names_both_versions() {
+        let err = GrammarError::AbiMismatch {
+            grammar: 16,
+            min: 13,
+            max: 15,
+        };
+        assert_eq!(
+            err.to_string(),
+            "bevyml grammar uses tree-sitter ABI 16, but the linked tree-sitter crate supports \
+             ABI 13 to 15; regenerate tree-sitter-bevyml or align the tree-sitter version"
+        );
+    }
+
+    #[test]
+    fn bundled_grammar_loads() {
+        assert!(BevymlParser::try_new().is_ok());
+    }
+}
@@ -0,0 +1,206 @@
+//! Attributes and style declarations as plain data, before they are turned into Bevy values. Nothing
+//! here depends on Bevy, so build scripts and WASM tooling can use these types with the `bevy`
+//! feature turned off.
+
+use smallvec::SmallVec;
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(bevy_reflect::Reflect))]
+pub struct ClassList<Str = String> {
+    pub raw: Str,
+    pub classes: SmallVec<[Str; 4]>,
+}
+
+impl<Str: AsRef<str>> ClassList<Str> {
+    /// Whether `class` is one of the names in the list; class names are case-sensitive.
+    pub fn contains(&self, class: &str) -> bool {
+        self.iter().any(|name| name == class)
+    }
+
+    /// The class names in source order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.classes.iter().map(AsRef::as_ref)
+    }
+}
+
+impl<'a> ClassList<Cow<'a, str>> {
+    pub fn parse(raw: Cow<'a, str>) -> Self {
+        match raw {
+            Cow::Borrowed(raw_ref) => {
+                let mut classes = SmallVec::new();
+                for class in raw_ref.split_whitespace() {
+                    if class.is_empty() {
+                        continue;
+                    }
+                    classes.push(Cow::Borrowed(class));
+                }
+                Self {
+                    raw: Cow::Borrowed(raw_ref),
+                    classes,
+                }
+            }
+            Cow::Owned(raw_string) => {
+                let mut classes = SmallVec::new();
+                for class in raw_string.split_whitespace() {
+                    if class.is_empty() {
+                        continue;
+                    }
+                    classes.push(Cow::Owned(class.to_string()));
+                }
+                Self {
+                    raw: Cow::Owned(raw_string),
+                    classes,
+                }
+            }
+        }
+    }
+
+    pub fn into_owned(self) -> ClassList<String> {
+        ClassList {
+            raw: self.raw.into_owned(),
+            classes: self.classes.into_iter().map(Cow::into_owned).collect(),
+        }
+    }
+}
+
+/// An attribute as written in the markup; `value` is `None` when it is bare, like `disabled`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawAttribute<Str = String> {
+    pub name: Str,
+    pub value: Option<Str>,
+}
+
+impl<Str: AsRef<str>> RawAttribute<Str> {
+    pub fn new(name: Str, value: Option<Str>) -> Self {
+        Self { name, value }
+    }
+
+    /// The value as a string, empty for a bare attribute.
+    pub fn value_str(&self) -> &str {
+        self.value.as_ref().map_or("", AsRef::as_ref)
+    }
+
+    /// The names listed by a `class` attribute, `None` for any other attribute.
+    pub fn class_list(&self) -> Option<ClassList<Cow<'_, str>>> {
+        self.name
+            .as_ref()
+            .eq_ignore_ascii_case("class")
+            .then(|| ClassList::parse(Cow::Borrowed(self.value_str())))
+    }
+
+    /// The declarations of a `style` attribute, see [`split_declarations`]; `None` for any other
+    /// attribute.
+    pub fn declarations(&self) -> Option<Vec<Result<RawDeclaration<'_>, DeclarationError<'_>>>> {
+        self.name
+            .as_ref()
+            .eq_ignore_ascii_case("style")
+            .then(|| split_declarations(self.value_str()).collect())
+    }
+}
+
+/// One `property: value` pair of a style, trimmed, with its `!important` flag split off. The
+/// property keeps the case it was written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RawDeclaration<'a> {
+    pub property: &'a str,
+    pub value: &'a str,
+    pub important: bool,
+}
+
+/// Why a declaration could not be split, with the text it refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeclarationError<'a> {
+    /// No `:` separates property and value; holds the whole declaration.
+    MissingColon(&'a str),
+    /// Nothing precedes the `:`; holds the whole declaration.
+    MissingProperty(&'a str),
+    /// Nothing but `!important`, if anything, follows the `:`; holds the property.
+    MissingValue(&'a str),
+}
+
+/// Splits a `style` value into its declarations in source order, skipping empty ones such as the
+/// one after a trailing `;`.
+pub fn split_declarations(
+    raw: &str,
+) -> impl Iterator<Item = Result<RawDeclaration<'_>, DeclarationError<'_>>> {
+    raw.split(';').filter_map(|declaration| {
+        let trimmed = declaration.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        let Some((property, value)) = trimmed.split_once(':') else {
+            return Some(Err(DeclarationError::MissingColon(trimmed)));
+        };
+        let property = property.trim();
+        if property.is_empty() {
+            return Some(Err(DeclarationError::MissingProperty(trimmed)));
+        }
+        let value = value.trim();
+        let stripped = strip_important(value);
+        if stripped.is_empty() {
+            return Some(Err(DeclarationError::MissingValue(property)));
+        }
+        Some(Ok(RawDeclaration {
+            property,
+            value: stripped,
+            important: stripped.len() != value.len(),
+        }))
+    })
+}
+
+fn strip_important(value: &str) -> &str {
+    let trimmed = value.trim();
+    if let Some(stripped) = trimmed.strip_suffix("!important") {
+        stripped.trim_end()
+    } else {
+        trimmed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_attributes_expose_classes_and_declarations() {
+        let class = RawAttribute::new("class", Some("card  wide"));
+        let classes = class.class_list().unwrap();
+        assert_eq!(classes.iter().collect::<Vec<_>>(), ["card", "wide"]);
+        assert!(class.declarations().is_none());
+
+        let style = RawAttribute::new("style", Some("Width: 10px; color: red !important;; gap"));
+        assert_eq!(
+            style.declarations().unwrap(),
+            [
+                Ok(RawDeclaration {
+                    property: "Width",
+                    value: "10px",
+                    important: false,
+                }),
+                Ok(RawDeclaration {
+                    property: "color",
+                    value: "red",
+                    important: true,
+                }),
+                Err(DeclarationError::MissingColon("gap")),
+            ]
+        );
+        assert!(RawAttribute::new("hidden", None).class_list().is_none());
+    }
+
+    #[test]
+    fn malformed_declarations_say_what_is_missing() {
+        let errors: Vec<_> = split_declarations(": red; color: !important; margin:")
+            .filter_map(Result::err)
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                DeclarationError::MissingProperty(": red"),
+                DeclarationError::MissingValue("color"),
+                DeclarationError::MissingValue("margin"),
+            ]
+        );
+    }
+}
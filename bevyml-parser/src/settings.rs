@@ -0,0 +1,141 @@
+use crate::inode::BASE_FONT_PX;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default cap on attributes kept per element, see [`ParseSettings::max_attributes`].
+pub const DEFAULT_MAX_ATTRIBUTES: usize = 1024;
+
+/// A sensible bound for [`ParseSettings::max_px`] when clamping is turned on.
+pub const DEFAULT_MAX_PX: f32 = 100_000.0;
+
+/// Legacy tags resolved to their modern equivalents unless [`ParseSettings::tag_aliases`] says
+/// otherwise.
+pub const DEFAULT_TAG_ALIASES: &[(&str, &str)] = &[
+    ("strike", "s"),
+    ("center", "div"),
+    ("dir", "ul"),
+    ("xmp", "pre"),
+    ("listing", "pre"),
+];
+
+/// Knobs that change how markup is turned into an [`ITree`](crate::itree::ITree).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParseSettings {
+    pub attribute_filter: AttributeFilter,
+    /// `<script>` subtrees are dropped unless the markup is trusted and this is turned on.
+    pub allow_scripts: bool,
+    /// Attributes past this count on a single element are discarded with a warning.
+    pub max_attributes: usize,
+    /// Factor applied to every `px` length in inline styles, e.g. `2.0` when authoring in physical
+    /// pixels for a 2x display. Percentages and viewport units are left alone.
+    pub px_scale: f32,
+    /// When set, inline style pixel lengths beyond this magnitude are clamped with a warning, so a
+    /// typo like `999999999px` cannot blow up the layout. Applied after `px_scale`.
+    pub max_px: Option<f32>,
+    /// Width of a tab stop when expanding tabs inside `<pre>`; elsewhere tabs collapse like any
+    /// other whitespace.
+    pub tab_size: usize,
+    /// Keeps text nodes exactly as written, whitespace-only ones included and entities such as
+    /// `&amp;` left undecoded, for tools that need the source text. Spawned trees still collapse
+    /// whitespace outside `<pre>`.
+    pub preserve_raw_text: bool,
+    /// Accepts documents with text but no elements at the top level, wrapping that text in an
+    /// implicit `<div>` instead of failing with
+    /// [`MissingRootElement`](crate::itree::ITreeError::MissingRootElement).
+    pub allow_text_root: bool,
+    /// Whether to parse as a scripting-capable host would. bevyml runs no scripts, so by default
+    /// `<noscript>` content is kept; turning this on drops it instead.
+    pub scripting_enabled: bool,
+    /// Reads boolean attributes like `disabled` as set only when bare, empty or repeating their
+    /// name, warning about any other value. Off by default, where only `false`, `0`, `no` and
+    /// `off` unset them.
+    pub strict_bool: bool,
+    /// Keeps inline `on*` handler attributes so trusted markup can name handlers the app
+    /// registered, see [`EventBindings`](crate::attributes::EventBindings). Off by default, where
+    /// they are dropped.
+    pub event_bindings: bool,
+    /// Deprecated or alternative tag names mapped to the tag they parse as, keyed in lowercase.
+    /// Starts out with [`DEFAULT_TAG_ALIASES`]; `center` also gets centered layout defaults.
+    pub tag_aliases: HashMap<String, String>,
+    /// Gives flex containers an explicit `align-items: stretch` and `justify-content: flex-start`,
+    /// the CSS initial values, wherever markup leaves them unset. Bevy's `Default` lays out the
+    /// same way, so this only matters to code that reads or compares the spawned `Node` values.
+    pub css_flex_alignment: bool,
+    /// Size in pixels that `rem` and `em` lengths in inline styles resolve against, and that
+    /// percentage font sizes scale at the top of the tree.
+    ///
+    /// `em` is treated as `rem` for every property, `font-size` included: Bevy has no
+    /// font-relative units and an element's own font size is not known while its style is parsed.
+    /// A size relative to the parent's font is written as a percentage, which the font-size
+    /// cascade resolves against the nearest ancestor.
+    pub root_font_size: f32,
+}
+
+impl Default for ParseSettings {
+    fn default() -> Self {
+        Self {
+            attribute_filter: AttributeFilter::default(),
+            allow_scripts: false,
+            max_attributes: DEFAULT_MAX_ATTRIBUTES,
+            px_scale: 1.0,
+            max_px: None,
+            tab_size: 4,
+            preserve_raw_text: false,
+            allow_text_root: false,
+            scripting_enabled: false,
+            strict_bool: false,
+            event_bindings: false,
+            tag_aliases: DEFAULT_TAG_ALIASES
+                .iter()
+                .map(|(alias, tag)| (alias.to_string(), tag.to_string()))
+                .collect(),
+            css_flex_alignment: false,
+            root_font_size: BASE_FONT_PX,
+        }
+    }
+}
+
+/// Decides which attributes survive parsing; patterns are case-insensitive and a trailing `*`
+/// matches any suffix, so `on*` covers every inline event handler.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum AttributeFilter {
+    #[default]
+    AllowAll,
+    Deny(Vec<String>),
+    Allow(Vec<String>),
+}
+
+impl ParseSettings {
+    /// The tag `tag_name` is an alias of, if any.
+    pub fn tag_alias(&self, tag_name: &str) -> Option<&str> {
+        self.tag_aliases
+            .get(&tag_name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+impl AttributeFilter {
+    pub fn permits(&self, name: &str) -> bool {
+        match self {
+            AttributeFilter::AllowAll => true,
+            AttributeFilter::Deny(patterns) => !matches_any(patterns, name),
+            AttributeFilter::Allow(patterns) => matches_any(patterns, name),
+        }
+    }
+}
+
+fn matches_any(patterns: &[String], name: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| matches_pattern(pattern, name))
+}
+
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name
+            .get(..prefix.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(prefix)),
+        None => pattern.eq_ignore_ascii_case(name),
+    }
+}
@@ -0,0 +1,757 @@
+//! Rules from `<style>` elements, matched against the parsed tree and cascaded below inline styles.
+//! A compound selector combines a type, ids and classes, as in `p`, `#main` or `div.card.wide`;
+//! compounds can be joined by the descendant (` `) and child (`>`) combinators, as in `nav a` or
+//! `ul > li`, and a rule may list several selectors separated by commas. A selector may end in a
+//! state pseudo-class like `:hover`; such rules are kept apart from the ones applied at parse time
+//! and only their colors follow the element's state at runtime. Likewise, only colors of the rules
+//! inside `@media (prefers-color-scheme: ...)` follow the [`ColorScheme`] resource.
+
+use std::borrow::Cow;
+
+use bevy_ecs::resource::Resource;
+use bevy_reflect::Reflect;
+
+use crate::{
+    attributes::{push_warning, split_top_level, StyleAttribute, StyleContext, StyleDeclaration},
+    inode::{NodeId, NodeType},
+    itree::ITree,
+};
+
+/// The rules of one or more stylesheets, in source order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stylesheet {
+    pub rules: Vec<StyleRule>,
+}
+
+/// Declarations applied to every element matching any of `selectors`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StyleRule {
+    pub selectors: Vec<Selector>,
+    pub style: StyleAttribute,
+    /// Set for rules inside `@media (prefers-color-scheme: ...)`, which only apply in that scheme.
+    pub color_scheme: Option<ColorScheme>,
+}
+
+/// The color scheme `@media (prefers-color-scheme: ...)` rules are evaluated against; insert it as
+/// a resource to switch the colors of spawned documents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Resource, Reflect)]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// Compound selectors joined by combinators, like `nav > ul a`; the last compound is matched
+/// against the element itself and the others against its ancestors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selector {
+    /// Compounds from left to right, never empty.
+    pub compounds: Vec<Compound>,
+    /// How each compound relates to the one after it, so there is one fewer than `compounds`.
+    pub combinators: Vec<Combinator>,
+}
+
+/// How the element matched by a compound relates to the one matched by the compound before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combinator {
+    /// Whitespace: any ancestor.
+    Descendant,
+    /// `>`: the parent.
+    Child,
+}
+
+/// A compound selector; an element matches when it has the type, when one is given, and every id
+/// and class listed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Compound {
+    /// Tag name in lowercase, `None` for `*` or when the selector starts with an id or class.
+    pub tag: Option<String>,
+    pub ids: Vec<String>,
+    pub classes: Vec<String>,
+    /// Only allowed on the last compound of a selector; [`Compound::matches`] ignores it.
+    pub pseudo_class: Option<PseudoClass>,
+}
+
+/// An element state a selector can require.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PseudoClass {
+    /// `:hover`, while the pointer is over the element.
+    Hover,
+    /// `:checked`, while a checkbox or radio button is checked.
+    Checked,
+    /// `:disabled`, while a control is disabled.
+    Disabled,
+}
+
+impl PseudoClass {
+    /// The pseudo-class named `name`, written without its colon, ignoring ASCII case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            ("hover", PseudoClass::Hover),
+            ("checked", PseudoClass::Checked),
+            ("disabled", PseudoClass::Disabled),
+        ]
+        .into_iter()
+        .find_map(|(known, state)| name.eq_ignore_ascii_case(known).then_some(state))
+    }
+}
+
+impl Stylesheet {
+    /// Parses CSS source, collecting problems into `cx.warnings`. Rules using selector syntax
+    /// beyond types, ids, classes, the descendant and child combinators and state pseudo-classes are
+    /// skipped as a whole, as browsers do with invalid selectors. `@media` blocks asking for
+    /// anything but `prefers-color-scheme`, and at-rules other than `@import` and `@media`, are
+    /// skipped with a warning.
+    pub fn parse(source: &str, cx: &mut StyleContext) -> Self {
+        let source = strip_comments(source);
+        let mut sheet = Stylesheet::default();
+        let mut rest = source.as_ref();
+        while let Some(start) = rest.find(|ch: char| !ch.is_whitespace()) {
+            rest = &rest[start..];
+            if let Some(after) = parse_media_rule(rest, &mut sheet, cx) {
+                rest = after;
+                continue;
+            }
+            if rest.starts_with('@') {
+                rest = skip_at_rule(rest, cx);
+                continue;
+            }
+            let Some(open) = rest.find('{') else {
+                push_warning(
+                    &mut cx.warnings,
+                    "",
+                    rest.trim(),
+                    format!("stylesheet rule {:?} has no declaration block", rest.trim()),
+                );
+                break;
+            };
+            let (block, after) = block_contents(&rest[open..]);
+            sheet
+                .rules
+                .extend(StyleRule::parse(&rest[..open], block, cx));
+            rest = after;
+        }
+        sheet
+    }
+
+    /// Cascades every rule matching `id` by the specificity of its most specific matching selector,
+    /// then source order, so more specific and later rules win unless a lower one is `!important`;
+    /// `None` when no rule matches. Only selectors ending in `state` count, or those without a
+    /// pseudo-class when it is `None`, and only `@media` rules for `scheme`.
+    pub fn matching_style(
+        &self,
+        itree: &ITree<'_>,
+        id: NodeId,
+        state: Option<PseudoClass>,
+        scheme: Option<ColorScheme>,
+    ) -> Option<StyleAttribute> {
+        let mut matching: Vec<_> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.color_scheme.is_none() || rule.color_scheme == scheme)
+            .filter_map(|rule| {
+                let specificity = rule
+                    .selectors
+                    .iter()
+                    .filter(|selector| {
+                        selector.pseudo_class() == state && selector.matches(itree, id)
+                    })
+                    .map(Selector::specificity)
+                    .max()?;
+                Some((specificity, rule))
+            })
+            .collect();
+        // Stable, so rules of equal specificity keep their source order.
+        matching.sort_by_key(|(specificity, _)| *specificity);
+        matching.into_iter().fold(None, |cascaded, (_, rule)| {
+            Some(match cascaded {
+                Some(lower) => lower.merge(&rule.style),
+                None => rule.style.clone(),
+            })
+        })
+    }
+
+    /// Whether an `@media (prefers-color-scheme: ...)` rule matches the element `id`, so its
+    /// colors depend on the [`ColorScheme`].
+    pub fn has_color_scheme_rules(&self, itree: &ITree<'_>, id: NodeId) -> bool {
+        self.rules.iter().any(|rule| {
+            rule.color_scheme.is_some()
+                && rule
+                    .selectors
+                    .iter()
+                    .any(|selector| selector.matches(itree, id))
+        })
+    }
+}
+
+impl StyleRule {
+    /// Parses a rule from its selector list and the contents of its declaration block.
+    pub fn parse(prelude: &str, block: &str, cx: &mut StyleContext) -> Option<Self> {
+        let prelude = prelude.trim();
+        let selectors = split_top_level(prelude, |ch| ch == ',')
+            .into_iter()
+            .map(Selector::parse)
+            .collect::<Option<Vec<_>>>()
+            .filter(|selectors| !selectors.is_empty());
+        let Some(selectors) = selectors else {
+            push_warning(
+                &mut cx.warnings,
+                "",
+                prelude,
+                format!("unsupported selector {:?}, skipping its rule", prelude),
+            );
+            return None;
+        };
+        let style = StyleAttribute::parse_with_warnings(Cow::Borrowed(block.trim()), cx);
+        let has_state = selectors
+            .iter()
+            .any(|selector| selector.pseudo_class().is_some());
+        let rule = StyleRule {
+            selectors,
+            style: style.into_owned(),
+            color_scheme: None,
+        };
+        if has_state && !rule.colors_only() {
+            push_warning(
+                &mut cx.warnings,
+                "",
+                prelude,
+                format!(
+                    "only background-color and color follow the state in {:?}, ignoring the rest",
+                    prelude
+                ),
+            );
+        }
+        Some(rule)
+    }
+
+    /// Whether every declaration is a `background-color` or `color`, the only ones applied at
+    /// runtime.
+    pub fn colors_only(&self) -> bool {
+        self.style.declarations.iter().all(|declaration| {
+            matches!(
+                declaration,
+                StyleDeclaration::BackgroundColor(_) | StyleDeclaration::TextColor(_)
+            )
+        })
+    }
+}
+
+impl Selector {
+    /// Parses a single selector, `None` when it uses anything but compounds joined by whitespace or
+    /// `>`, or a pseudo-class before its last compound.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut rest = text.trim();
+        if rest.is_empty() {
+            return None;
+        }
+        let mut selector = Selector {
+            compounds: Vec::new(),
+            combinators: Vec::new(),
+        };
+        loop {
+            let end = rest
+                .find(|ch: char| ch.is_whitespace() || ch == '>')
+                .unwrap_or(rest.len());
+            selector.compounds.push(Compound::parse(&rest[..end])?);
+            let after = rest[end..].trim_start();
+            if after.is_empty() {
+                let (_, ancestors) = selector.compounds.split_last()?;
+                return ancestors
+                    .iter()
+                    .all(|compound| compound.pseudo_class.is_none())
+                    .then_some(selector);
+            }
+            rest = match after.strip_prefix('>') {
+                Some(after) => {
+                    selector.combinators.push(Combinator::Child);
+                    after.trim_start()
+                }
+                None => {
+                    selector.combinators.push(Combinator::Descendant);
+                    after
+                }
+            };
+        }
+    }
+
+    /// How many ids, classes and pseudo-classes, and types the compounds name, in that order;
+    /// comparing these tuples ranks selectors as CSS does.
+    pub fn specificity(&self) -> (u16, u16, u16) {
+        let count = |len: usize| u16::try_from(len).unwrap_or(u16::MAX);
+        self.compounds
+            .iter()
+            .fold((0, 0, 0), |(ids, classes, types), compound| {
+                (
+                    ids.saturating_add(count(compound.ids.len())),
+                    classes.saturating_add(count(
+                        compound.classes.len() + usize::from(compound.pseudo_class.is_some()),
+                    )),
+                    types.saturating_add(count(usize::from(compound.tag.is_some()))),
+                )
+            })
+    }
+
+    /// The pseudo-class of the last compound, which the element's state must have for the rule to
+    /// apply.
+    pub fn pseudo_class(&self) -> Option<PseudoClass> {
+        self.compounds
+            .last()
+            .and_then(|compound| compound.pseudo_class)
+    }
+
+    /// Whether the element `id` matches, ignoring the pseudo-class, walking up its ancestors for the compounds before the
+    /// last.
+    pub fn matches(&self, itree: &ITree<'_>, id: NodeId) -> bool {
+        self.matches_from(itree, self.compounds.len() - 1, id)
+    }
+
+    /// Whether `id` matches the compound at `index` and its ancestors the compounds before it.
+    fn matches_from(&self, itree: &ITree<'_>, index: usize, id: NodeId) -> bool {
+        if !self.compounds[index].matches(itree, id) {
+            return false;
+        }
+        let Some(previous) = index.checked_sub(1) else {
+            return true;
+        };
+        let mut parent = itree.node(id).parent;
+        match self.combinators[previous] {
+            Combinator::Child => {
+                parent.is_some_and(|parent| self.matches_from(itree, previous, parent))
+            }
+            Combinator::Descendant => {
+                while let Some(ancestor) = parent {
+                    if self.matches_from(itree, previous, ancestor) {
+                        return true;
+                    }
+                    parent = itree.node(ancestor).parent;
+                }
+                false
+            }
+        }
+    }
+}
+
+impl Compound {
+    /// Parses a single compound, `None` when it is empty or uses anything but a type or `*`, ids,
+    /// classes and one known pseudo-class.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut rest = text;
+        if rest.is_empty() {
+            return None;
+        }
+        let mut compound = Compound::default();
+        if let Some(after) = rest.strip_prefix('*') {
+            rest = after;
+        } else {
+            let (tag, after) = split_ident(rest);
+            if !tag.is_empty() {
+                compound.tag = Some(tag.to_ascii_lowercase());
+            }
+            rest = after;
+        }
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix(':') {
+                let (name, after) = split_ident(after);
+                if compound.pseudo_class.is_some() {
+                    return None;
+                }
+                compound.pseudo_class = Some(PseudoClass::from_name(name)?);
+                rest = after;
+                continue;
+            }
+            let (list, after) = if let Some(after) = rest.strip_prefix('.') {
+                (&mut compound.classes, after)
+            } else if let Some(after) = rest.strip_prefix('#') {
+                (&mut compound.ids, after)
+            } else {
+                return None;
+            };
+            let (name, after) = split_ident(after);
+            if name.is_empty() {
+                return None;
+            }
+            list.push(name.to_string());
+            rest = after;
+        }
+        Some(compound)
+    }
+
+    /// Whether the element `id` matches. Text nodes never do.
+    pub fn matches(&self, itree: &ITree<'_>, id: NodeId) -> bool {
+        let node = itree.node(id);
+        node.node_type != NodeType::Text
+            && self
+                .tag
+                .as_ref()
+                .is_none_or(|tag| node.node_type.tag_name().eq_ignore_ascii_case(tag))
+            && self
+                .ids
+                .iter()
+                .all(|id| node.attributes.id() == Some(id.as_str()))
+            && self
+                .classes
+                .iter()
+                .all(|class| node.attributes.has_class(class))
+    }
+}
+
+/// Splits a leading CSS identifier off `text`; escapes are not supported.
+fn split_ident(text: &str) -> (&str, &str) {
+    let end = text
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' || !ch.is_ascii()))
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
+
+/// Replaces every `/* ... */` comment with a space; an unclosed comment runs to the end.
+fn strip_comments(source: &str) -> Cow<'_, str> {
+    if !source.contains("/*") {
+        return Cow::Borrowed(source);
+    }
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        stripped.push(' ');
+        let comment = &rest[start + 2..];
+        rest = comment.find("*/").map_or("", |end| &comment[end + 2..]);
+    }
+    stripped.push_str(rest);
+    Cow::Owned(stripped)
+}
+
+/// Splits `text`, which starts with `{`, into the contents of that block and what follows it. An
+/// unclosed block runs to the end, as in CSS.
+fn block_contents(text: &str) -> (&str, &str) {
+    let mut depth = 0usize;
+    for (index, ch) in text.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (&text[1..index], &text[index + 1..]);
+                }
+            }
+            _ => {}
+        }
+    }
+    (&text[1..], "")
+}
+
+/// Parses the `@media` block `text` starts with into `sheet`, returning what follows it; `None`
+/// when `text` does not start with a `@media` block. Blocks whose query is not a single
+/// `prefers-color-scheme` feature are skipped with a warning, as are state rules inside them.
+fn parse_media_rule<'a>(
+    text: &'a str,
+    sheet: &mut Stylesheet,
+    cx: &mut StyleContext,
+) -> Option<&'a str> {
+    let query = text
+        .get(..6)
+        .filter(|name| name.eq_ignore_ascii_case("@media"))
+        .map(|_| &text[6..])
+        .filter(|query| query.starts_with(|ch: char| ch.is_whitespace() || ch == '('))?;
+    let open = query.find(['{', ';'])?;
+    if !query[open..].starts_with('{') {
+        return None;
+    }
+    let (block, after) = block_contents(&query[open..]);
+    let query = query[..open].trim();
+    let Some(scheme) = media_color_scheme(query) else {
+        push_warning(
+            &mut cx.warnings,
+            "",
+            query,
+            format!("unsupported media query {query:?}, skipping its rules"),
+        );
+        return Some(after);
+    };
+    let mut colors_only = true;
+    for mut rule in Stylesheet::parse(block, cx).rules {
+        if rule
+            .selectors
+            .iter()
+            .any(|selector| selector.pseudo_class().is_some())
+        {
+            push_warning(
+                &mut cx.warnings,
+                "",
+                query,
+                format!("state rules inside @media {query} are not supported, skipping them"),
+            );
+            continue;
+        }
+        colors_only &= rule.colors_only();
+        rule.color_scheme = Some(scheme);
+        sheet.rules.push(rule);
+    }
+    if !colors_only {
+        push_warning(
+            &mut cx.warnings,
+            "",
+            query,
+            format!("only background-color and color follow @media {query}, ignoring the rest"),
+        );
+    }
+    Some(after)
+}
+
+/// The scheme a media query of the form `(prefers-color-scheme: dark)` asks for.
+fn media_color_scheme(query: &str) -> Option<ColorScheme> {
+    let (feature, value) = query
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .split_once(':')?;
+    if !feature.trim().eq_ignore_ascii_case("prefers-color-scheme") {
+        return None;
+    }
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("light") {
+        Some(ColorScheme::Light)
+    } else if value.eq_ignore_ascii_case("dark") {
+        Some(ColorScheme::Dark)
+    } else {
+        None
+    }
+}
+
+/// Skips the at-rule `text` starts with, up to its `;` or past its block. `@import` is expected and
+/// handled by the asset loader; any other at-rule is reported.
+fn skip_at_rule<'a>(text: &'a str, cx: &mut StyleContext) -> &'a str {
+    let name_end = text[1..]
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '-'))
+        .map_or(text.len(), |end| end + 1);
+    let name = &text[..name_end];
+    if !name.eq_ignore_ascii_case("@import") {
+        push_warning(
+            &mut cx.warnings,
+            "",
+            name,
+            format!("unsupported at-rule {name}, skipping it"),
+        );
+    }
+    match text.find([';', '{']) {
+        Some(end) if text[end..].starts_with('{') => block_contents(&text[end..]).1,
+        Some(end) => &text[end + 1..],
+        None => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inode::{HoverStyle, StateColors},
+        BevymlParser,
+    };
+    use bevy_color::Color;
+    use bevy_ui::Val;
+
+    #[test]
+    fn selector_lists_apply_the_rule_to_every_listed_selector() {
+        let itree = BevymlParser::new()
+            .parse(
+                r#"<div><style>.a, #b { width: 10px }</style><p class="a">a</p><p id="b">b</p><p>c</p></div>"#,
+            )
+            .unwrap();
+        let widths: Vec<_> = itree
+            .child_nodes(itree.roots[0])
+            .filter(|child| child.node_type == NodeType::P)
+            .map(|child| child.to_bundle().node.width)
+            .collect();
+        assert_eq!(widths, [Val::Px(10.0), Val::Px(10.0), Val::Auto]);
+    }
+
+    #[test]
+    fn compound_selectors_parse_types_ids_and_classes() {
+        assert_eq!(
+            Compound::parse("DIV.card#main.wide"),
+            Some(Compound {
+                tag: Some("div".to_string()),
+                ids: vec!["main".to_string()],
+                classes: vec!["card".to_string(), "wide".to_string()],
+                pseudo_class: None,
+            })
+        );
+        assert_eq!(Compound::parse("*"), Some(Compound::default()));
+        assert_eq!(Selector::parse(""), None);
+        assert_eq!(Selector::parse("a[href]"), None);
+        assert_eq!(Selector::parse(".a."), None);
+    }
+
+    #[test]
+    fn combinators_split_selectors_into_compounds() {
+        let selector = Selector::parse("nav  ul>li .x").unwrap();
+        assert_eq!(selector.compounds.len(), 4);
+        assert_eq!(
+            selector.combinators,
+            [
+                Combinator::Descendant,
+                Combinator::Child,
+                Combinator::Descendant
+            ]
+        );
+        assert_eq!(Selector::parse("ul > li"), Selector::parse("ul>li"));
+        assert_eq!(Selector::parse("> li"), None);
+        assert_eq!(Selector::parse("ul >"), None);
+        assert_eq!(Selector::parse("ul > > li"), None);
+    }
+
+    #[test]
+    fn descendant_selectors_match_only_inside_their_ancestor() {
+        let itree = BevymlParser::new()
+            .parse(
+                r#"<div><style>nav a { color: red }</style><nav><p><a href="x">in</a></p></nav><a href="y">out</a></div>"#,
+            )
+            .unwrap();
+        let colors: Vec<_> = itree
+            .nodes
+            .iter()
+            .filter(|node| node.node_type == NodeType::A)
+            .map(|node| node.text_color())
+            .collect();
+        assert_eq!(colors, [Some(Color::srgb(1.0, 0.0, 0.0)), None]);
+    }
+
+    #[test]
+    fn child_selectors_match_only_direct_children() {
+        let itree = BevymlParser::new()
+            .parse(
+                r#"<div><style>div > p { width: 10px }</style><p>direct</p><section><p>nested</p></section></div>"#,
+            )
+            .unwrap();
+        let widths: Vec<_> = itree
+            .nodes
+            .iter()
+            .filter(|node| node.node_type == NodeType::P)
+            .map(|node| node.to_bundle().node.width)
+            .collect();
+        assert_eq!(widths, [Val::Px(10.0), Val::Auto]);
+    }
+
+    #[test]
+    fn rules_skip_comments_at_rules_and_bad_selectors() {
+        let mut cx = StyleContext::default();
+        let sheet = Stylesheet::parse(
+            r#"@import "base.css";
+            /* p { width: 1px } */
+            @font-face { font-family: x; }
+            p { width: 2px }
+            a[href] { width: 3px }
+            .x { width: 4px"#,
+            &mut cx,
+        );
+        let widths: Vec<_> = sheet
+            .rules
+            .iter()
+            .map(|rule| rule.style.declarations[..].to_vec())
+            .collect();
+        assert_eq!(
+            widths,
+            [
+                vec![StyleDeclaration::Width(Val::Px(2.0))],
+                vec![StyleDeclaration::Width(Val::Px(4.0))],
+            ]
+        );
+        assert_eq!(cx.warnings.len(), 2);
+    }
+
+    #[test]
+    fn inline_styles_beat_sheet_rules_unless_important() {
+        let itree = BevymlParser::new()
+            .parse(
+                r#"<div><style>p { width: 10px; height: 5px !important }</style><p style="width: 20px; height: 30px">a</p></div>"#,
+            )
+            .unwrap();
+        let p = itree
+            .child_nodes(itree.roots[0])
+            .find(|child| child.node_type == NodeType::P)
+            .unwrap();
+        let node = p.to_bundle().node;
+        assert_eq!(node.width, Val::Px(20.0));
+        assert_eq!(node.height, Val::Px(5.0));
+    }
+
+    #[test]
+    fn hover_rules_are_kept_apart_from_the_parsed_style() {
+        let itree = BevymlParser::new()
+            .parse(
+                r#"<div><style>button { background-color: red } .go:HOVER { background-color: blue; width: 9px }</style><button class="go">Go</button></div>"#,
+            )
+            .unwrap();
+        let roots: Vec<crate::inode::BevyNodeTree> = itree.into();
+        let button = roots[0]
+            .children
+            .iter()
+            .find(|child| child.node.node_kind.kind == NodeType::Button)
+            .unwrap();
+        assert_eq!(button.node.background_color.0, Color::srgb(1.0, 0.0, 0.0));
+        assert_eq!(button.node.node.width, Val::Auto);
+        assert_eq!(
+            button.hover_style,
+            Some(HoverStyle(StateColors {
+                background_color: Some(Color::srgb(0.0, 0.0, 1.0)),
+                text_color: None,
+            }))
+        );
+        assert_eq!(Selector::parse("a:hover b"), None);
+        assert_eq!(Selector::parse("a:hover:hover"), None);
+        assert_eq!(Selector::parse("a:visited"), None);
+        assert_eq!(
+            Selector::parse("input:Checked").and_then(|selector| selector.pseudo_class()),
+            Some(PseudoClass::Checked)
+        );
+        assert_eq!(
+            Selector::parse(":disabled").and_then(|selector| selector.pseudo_class()),
+            Some(PseudoClass::Disabled)
+        );
+    }
+
+    #[test]
+    fn specificity_counts_ids_then_classes_then_types() {
+        let specificity = |text| Selector::parse(text).unwrap().specificity();
+        assert_eq!(specificity("#a"), (1, 0, 0));
+        assert_eq!(specificity(".b.c"), (0, 2, 0));
+        assert_eq!(specificity("div p"), (0, 0, 2));
+        assert_eq!(specificity("* > a:hover"), (0, 1, 1));
+        assert!(specificity("#a") > specificity(".b.c"));
+        assert!(specificity(".b.c") > specificity("div p"));
+    }
+
+    #[test]
+    fn more_specific_rules_win_unless_a_lower_one_is_important() {
+        let itree = BevymlParser::new()
+            .parse(
+                r#"<div><style>#a { width: 1px } .b.c { width: 2px; height: 2px !important } div p { width: 3px; height: 3px }</style><p id="a" class="b c">x</p></div>"#,
+            )
+            .unwrap();
+        let p = itree
+            .child_nodes(itree.roots[0])
+            .find(|child| child.node_type == NodeType::P)
+            .unwrap();
+        let node = p.to_bundle().node;
+        assert_eq!(node.width, Val::Px(1.0));
+        assert_eq!(node.height, Val::Px(2.0));
+    }
+
+    #[test]
+    fn color_scheme_media_rules_are_tagged_and_others_skipped() {
+        let mut cx = StyleContext::default();
+        let sheet = Stylesheet::parse(
+            "p { color: red }
+            @media (prefers-color-scheme: dark) { p { color: white } a:hover { color: blue } }
+            @MEDIA ( Prefers-Color-Scheme : Light ) { p { width: 1px } }
+            @media (min-width: 600px) { p { color: green } }",
+            &mut cx,
+        );
+        let schemes: Vec<_> = sheet.rules.iter().map(|rule| rule.color_scheme).collect();
+        assert_eq!(
+            schemes,
+            [None, Some(ColorScheme::Dark), Some(ColorScheme::Light)]
+        );
+        // The state rule, the width and the unsupported query.
+        assert_eq!(cx.warnings.len(), 3);
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use bevyml_parser::inode::{Disclosure, DisclosureBody};
+
+/// Shows or hides the body of every `<details>` whose [`Disclosure`] was spawned or toggled.
+pub(crate) fn sync_disclosures(
+    disclosures: Query<(&Disclosure, &Children), Changed<Disclosure>>,
+    mut bodies: Query<(&DisclosureBody, &mut Node)>,
+) {
+    for (disclosure, children) in &disclosures {
+        let mut iter = bodies.iter_many_mut(children);
+        while let Some((body, mut node)) = iter.fetch_next() {
+            node.display = if disclosure.open {
+                body.0
+            } else {
+                Display::None
+            };
+        }
+    }
+}
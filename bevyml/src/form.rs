@@ -0,0 +1,137 @@
+use bevyml_parser::attributes::{Attribute, Attributes};
+use bevyml_parser::inode::{BevyNodeTree, NodeType};
+
+/// Finds the `<form id="form_id">` among `roots` and collects the initial `name`/`value` pairs of
+/// its controls, following what a browser would submit: disabled and unnamed controls are
+/// skipped, unchecked checkboxes and radios are left out, and checked ones default to `on`.
+pub(crate) fn form_values(roots: &[BevyNodeTree], form_id: &str) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    if let Some(form) = roots.iter().find_map(|root| find_form(root, form_id)) {
+        collect_controls(form, &mut values);
+    }
+    values
+}
+
+fn find_form<'tree>(tree: &'tree BevyNodeTree, form_id: &str) -> Option<&'tree BevyNodeTree> {
+    let attributes = &tree.node.attributes;
+    if tree.node.node_kind.kind == NodeType::Form && attributes.id() == Some(form_id) {
+        return Some(tree);
+    }
+    tree.children
+        .iter()
+        .find_map(|child| find_form(child, form_id))
+}
+
+fn collect_controls(tree: &BevyNodeTree, values: &mut Vec<(String, String)>) {
+    for child in &tree.children {
+        let attributes = &child.node.attributes;
+        let control = matches!(
+            child.node.node_kind.kind,
+            NodeType::Input | NodeType::Select | NodeType::Textarea
+        );
+        if !control {
+            collect_controls(child, values);
+            continue;
+        }
+        if flag(attributes, |attribute| {
+            matches!(attribute, Attribute::Disabled(true))
+        }) {
+            continue;
+        }
+        let Some(name) = name_of(attributes) else {
+            continue;
+        };
+        if let Some(value) = control_value(child) {
+            values.push((name.to_string(), value));
+        }
+    }
+}
+
+fn control_value(control: &BevyNodeTree) -> Option<String> {
+    let attributes = &control.node.attributes;
+    match control.node.node_kind.kind {
+        NodeType::Input => {
+            let toggle = type_of(attributes).is_some_and(|input_type| {
+                input_type.eq_ignore_ascii_case("checkbox")
+                    || input_type.eq_ignore_ascii_case("radio")
+            });
+            if toggle
+                && !flag(attributes, |attribute| {
+                    matches!(attribute, Attribute::Checked(true))
+                })
+            {
+                return None;
+            }
+            let fallback = if toggle { "on" } else { "" };
+            Some(value_of(attributes).unwrap_or(fallback).to_string())
+        }
+        NodeType::Textarea => Some(text_content(control)),
+        NodeType::Select => {
+            let options: Vec<&BevyNodeTree> = control
+                .children
+                .iter()
+                .filter(|child| child.node.node_kind.kind == NodeType::Option)
+                .collect();
+            let selected = options
+                .iter()
+                .rev()
+                .find(|option| {
+                    flag(&option.node.attributes, |attribute| {
+                        matches!(attribute, Attribute::Selected(true))
+                    })
+                })
+                .or_else(|| options.first())?;
+            Some(
+                value_of(&selected.node.attributes)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| text_content(selected)),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn text_content(tree: &BevyNodeTree) -> String {
+    let mut content = String::new();
+    if let Some(text) = &tree.text {
+        content.push_str(&text.0);
+    }
+    for child in &tree.children {
+        content.push_str(&text_content(child));
+    }
+    content
+}
+
+fn flag(attributes: &Attributes, predicate: impl Fn(&Attribute) -> bool) -> bool {
+    attributes.items.iter().any(predicate)
+}
+
+fn name_of(attributes: &Attributes) -> Option<&str> {
+    attributes
+        .items
+        .iter()
+        .find_map(|attribute| match attribute {
+            Attribute::Name(name) if !name.is_empty() => Some(name.as_str()),
+            _ => None,
+        })
+}
+
+fn type_of(attributes: &Attributes) -> Option<&str> {
+    attributes
+        .items
+        .iter()
+        .find_map(|attribute| match attribute {
+            Attribute::Type(input_type) => Some(input_type.as_str()),
+            _ => None,
+        })
+}
+
+fn value_of(attributes: &Attributes) -> Option<&str> {
+    attributes
+        .items
+        .iter()
+        .find_map(|attribute| match attribute {
+            Attribute::Value(value) => Some(value.as_str()),
+            _ => None,
+        })
+}
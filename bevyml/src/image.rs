@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use bevyml_parser::inode::ImageSource;
+
+/// Loads the image behind every newly spawned [`ImageSource`] into an `ImageNode`.
+pub(crate) fn load_image_sources(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    sources: Query<(Entity, &ImageSource), Added<ImageSource>>,
+) {
+    for (entity, source) in &sources {
+        commands
+            .entity(entity)
+            .insert(ImageNode::new(asset_server.load(source.0.clone())));
+    }
+}
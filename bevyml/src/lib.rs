@@ -1,14 +1,127 @@
 use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use bevy::input_focus::InputFocus;
 use bevy::prelude::*;
 use bevyml_parser::BevymlParser;
-pub use bevyml_parser::attributes::{Attribute, Attributes};
-pub use bevyml_parser::inode::{BevyNodeTree, NodeKind, NodeType};
+pub use bevyml_parser::attributes::{
+    Attribute, AttributeOptions, Attributes, ClassList, CursorStyle, DataSet, EventBindings,
+    FontFamily, Hidden, TextTransform, TransitionEasing, TransitionSpec,
+};
+pub use bevyml_parser::inode::{
+    Autofocus, BevyNodeTree, CellSpan, CheckedStyle, ColorSchemeStyle, DisabledStyle, Disclosure,
+    DisclosureBody, HiddenUntilFound, HoverStyle, INodeBundle, ImageSource, Inert, MaxLines,
+    NoTranslate, NodeId, NodeKind, NodeType, ProgressState, StateColors, TextRun, TextSection,
+    ToggleControl,
+};
 use bevyml_parser::itree::ITreeError;
-use std::{error::Error, fmt, str};
+pub use bevyml_parser::settings::{AttributeFilter, ParseSettings};
+pub use bevyml_parser::stylesheet::ColorScheme;
+use std::{collections::HashMap, error::Error, fmt, str};
+
+mod disclosure;
+mod form;
+mod image;
+mod nesting;
+mod query;
+mod spawn;
+mod state_style;
+mod stylesheet;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+mod text;
+
+pub use nesting::NestingWarning;
+pub use query::BevymlQuery;
+pub use spawn::{spawn_bevy_tree, spawn_bevy_tree_child};
+pub use stylesheet::{BevymlStylesheet, BevymlStylesheetLoader};
+pub use text::BevymlTextDefaults;
 
 #[derive(Asset, TypePath, Debug)]
 pub struct BevymlAsset {
     pub roots: Vec<BevyNodeTree>,
+    /// Sheets from `<link rel="stylesheet">` and `@import` in `<style>`, in document order.
+    #[dependency]
+    pub stylesheets: Vec<Handle<BevymlStylesheet>>,
+}
+
+impl BevymlAsset {
+    /// Deterministic dump of every root, see [`BevyNodeTree::canonical_debug`].
+    pub fn canonical_debug(&self) -> String {
+        self.roots
+            .iter()
+            .map(BevyNodeTree::canonical_debug)
+            .collect()
+    }
+
+    /// Visible text of every root in document order, with its inherited color and font size
+    /// resolved; see [`BevyNodeTree::text_runs`] to include hidden text as well.
+    pub fn text_runs(&self) -> Vec<TextRun> {
+        self.roots
+            .iter()
+            .flat_map(BevyNodeTree::text_runs)
+            .filter(|run| run.visible)
+            .collect()
+    }
+
+    /// Swaps the element with the given `id`, and everything below it, for `new_tree`. Returns
+    /// `false` when no element carries that id.
+    pub fn replace_subtree(&mut self, id: &str, new_tree: BevyNodeTree) -> bool {
+        match self
+            .roots
+            .iter_mut()
+            .find_map(|root| root.find_by_id_mut(id))
+        {
+            Some(subtree) => {
+                *subtree = new_tree;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// How many elements of each type the document holds, text nodes excluded. Custom elements
+    /// are keyed by their tag name.
+    pub fn histogram(&self) -> HashMap<NodeType, usize> {
+        let mut counts = HashMap::new();
+        let mut stack: Vec<&BevyNodeTree> = self.roots.iter().collect();
+        while let Some(tree) = stack.pop() {
+            let kind = &tree.node.node_kind.kind;
+            if *kind != NodeType::Text {
+                *counts.entry(kind.clone()).or_insert(0) += 1;
+            }
+            stack.extend(&tree.children);
+        }
+        counts
+    }
+
+    /// Every element and text node as `(parent index, bundle, text)`, parents always listed before
+    /// their children, for spawning large documents with `spawn_batch` and linking the hierarchy
+    /// afterwards. Only the bundle and text are included; the extra components
+    /// [`spawn_bevy_tree`] inserts are not, and rich text keeps a node per inline element rather
+    /// than being joined into spans.
+    pub fn flatten(&self) -> Vec<(Option<usize>, INodeBundle, Option<Text>)> {
+        let mut flat = Vec::new();
+        let mut stack: Vec<(Option<usize>, &BevyNodeTree)> =
+            self.roots.iter().rev().map(|root| (None, root)).collect();
+        while let Some((parent, tree)) = stack.pop() {
+            let index = flat.len();
+            flat.push((parent, tree.node.clone(), tree.text.clone()));
+            stack.extend(tree.children.iter().rev().map(|child| (Some(index), child)));
+        }
+        flat
+    }
+
+    /// Elements sitting where their type does not belong, such as an `<li>` outside a list or a
+    /// `<td>` outside a row. Only clear-cut structural mistakes are reported; misplaced roots are
+    /// flagged too, so fragments meant to be spawned under a list or row will show up here.
+    pub fn validate_nesting(&self) -> Vec<NestingWarning> {
+        nesting::validate_nesting(&self.roots)
+    }
+
+    /// Initial `name`/`value` pairs of the controls inside `<form id="form_id">`, read from the
+    /// markup; live values are held by components once the tree is spawned.
+    pub fn form_values(&self, form_id: &str) -> Vec<(String, String)> {
+        form::form_values(&self.roots, form_id)
+    }
 }
 
 #[derive(Default, TypePath)]
@@ -54,22 +167,30 @@ impl From<ITreeError> for BevymlAssetLoaderError {
 
 impl AssetLoader for BevymlAssetLoader {
     type Asset = BevymlAsset;
-    type Settings = ();
+    type Settings = ParseSettings;
     type Error = BevymlAssetLoaderError;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &(),
-        _load_context: &mut LoadContext<'_>,
+        settings: &ParseSettings,
+        load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
         let source = str::from_utf8(&bytes)?;
-        let mut parser = BevymlParser::new();
+        let mut parser = BevymlParser::new().with_settings(settings.clone());
         let tree = parser.parse(source)?;
+        if tree.error_node_count > 0 {
+            warn!(
+                "bevyml asset contains {} syntax error(s), parsed with recovery",
+                tree.error_node_count
+            );
+        }
         tree.pretty_log();
-        Ok(BevymlAsset { roots: tree.into() })
+        let roots: Vec<BevyNodeTree> = tree.into();
+        let stylesheets = stylesheet::load_document_stylesheets(&roots, load_context);
+        Ok(BevymlAsset { roots, stylesheets })
     }
 
     fn extensions(&self) -> &[&str] {
@@ -82,6 +203,129 @@ pub struct BevymlAssetPlugin;
 impl Plugin for BevymlAssetPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<BevymlAsset>()
-            .init_asset_loader::<BevymlAssetLoader>();
+            .init_asset::<BevymlStylesheet>()
+            .init_asset_loader::<BevymlAssetLoader>()
+            .init_asset_loader::<BevymlStylesheetLoader>()
+            .init_resource::<BevymlTextDefaults>()
+            .init_resource::<ColorScheme>()
+            .init_resource::<InputFocus>()
+            .add_systems(
+                PostUpdate,
+                (
+                    text::apply_text_defaults.before(bevy::ui::UiSystems::Content),
+                    spawn::focus_autofocus_elements,
+                    image::load_image_sources,
+                    disclosure::sync_disclosures,
+                    (state_style::sync_checked, state_style::apply_state_styles)
+                        .chain()
+                        .after(text::apply_text_defaults),
+                ),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(source: &str) -> BevymlAsset {
+        BevymlAsset {
+            roots: BevymlParser::new().parse(source).unwrap().into(),
+            stylesheets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn canonical_debug_matches_golden() {
+        let fixture = asset(
+            r#"<div title="card" id="main" class="b a"><p>Hello <b>world</b></p><input disabled type="text" /></div>"#,
+        );
+        let golden = r#"div class="b a" id="main" title="card"
+  p
+    "Hello"
+    b
+      "world"
+  input disabled type="text"
+"#;
+        assert_eq!(fixture.canonical_debug(), golden);
+    }
+
+    #[test]
+    fn form_values_reads_named_input_defaults() {
+        let fixture = asset(
+            r#"<div><form id="login"><input name="user" value="ada" /><input name="remember" type="checkbox" checked /><input value="unnamed" /></form><input name="outside" value="x" /></div>"#,
+        );
+        assert_eq!(
+            fixture.form_values("login"),
+            [
+                ("user".to_string(), "ada".to_string()),
+                ("remember".to_string(), "on".to_string()),
+            ]
+        );
+        assert!(fixture.form_values("missing").is_empty());
+    }
+
+    #[test]
+    fn replace_subtree_swaps_the_element_with_that_id() {
+        let mut fixture = asset(r#"<div><h1>Title</h1><div id="content"><p>old</p></div></div>"#);
+        let new_tree = asset(r#"<div id="content"><ul><li>a</li><li>b</li></ul></div>"#)
+            .roots
+            .remove(0);
+        assert!(fixture.replace_subtree("content", new_tree));
+        assert!(!fixture.replace_subtree("missing", asset("<p>x</p>").roots.remove(0)));
+
+        let content = fixture.roots[0].find_by_id("content").unwrap();
+        assert_eq!(content.children.len(), 1);
+        assert_eq!(content.children[0].node.node_kind.kind, NodeType::Ul);
+        assert_eq!(content.children[0].children.len(), 2);
+    }
+
+    #[test]
+    fn histogram_counts_elements_by_type() {
+        let counts =
+            asset("<div><p>a</p><p>b <b>c</b></p><my-card></my-card><my-card></my-card></div>")
+                .histogram();
+        assert_eq!(counts[&NodeType::Div], 1);
+        assert_eq!(counts[&NodeType::P], 2);
+        assert_eq!(counts[&NodeType::B], 1);
+        assert_eq!(counts[&NodeType::Custom("my-card".to_string())], 2);
+        assert!(!counts.contains_key(&NodeType::Text));
+    }
+
+    #[test]
+    fn flatten_lists_every_node_after_its_parent() {
+        let fixture = asset("<div><p>a <b>b</b></p><span>c</span></div><p>d</p>");
+        let flat = fixture.flatten();
+        let node_count: usize = {
+            fn count(tree: &BevyNodeTree) -> usize {
+                1 + tree.children.iter().map(count).sum::<usize>()
+            }
+            fixture.roots.iter().map(count).sum()
+        };
+        assert_eq!(flat.len(), node_count);
+        for (index, (parent, _, _)) in flat.iter().enumerate() {
+            assert!(parent.is_none_or(|parent| parent < index));
+        }
+        let roots: Vec<_> = flat
+            .iter()
+            .filter(|(parent, _, _)| parent.is_none())
+            .map(|(_, bundle, _)| bundle.node_kind.kind.clone())
+            .collect();
+        assert_eq!(roots, [NodeType::Div, NodeType::P]);
+    }
+
+    #[test]
+    fn text_runs_resolve_inherited_color_and_size() {
+        let runs =
+            asset(r#"<p style="color: red; font-size: 20px">Hello <b>there</b></p>"#).text_runs();
+        let texts: Vec<_> = runs.iter().map(|run| run.text.as_str()).collect();
+        assert_eq!(texts, ["Hello", "there"]);
+        for run in &runs {
+            assert_eq!(
+                run.color,
+                Some(Color::from(bevy::color::palettes::basic::RED))
+            );
+            assert_eq!(run.font_size, Some(20.0));
+        }
     }
 }
@@ -0,0 +1,112 @@
+use bevyml_parser::inode::{BevyNodeTree, NodeId, NodeType};
+use std::fmt;
+
+/// An element placed where its type does not belong, such as a `<td>` outside a `<tr>`; see
+/// [`BevymlAsset::validate_nesting`](crate::BevymlAsset::validate_nesting).
+#[derive(Clone, Debug, PartialEq)]
+pub struct NestingWarning {
+    pub node_id: NodeId,
+    pub node_type: NodeType,
+    /// Type of the enclosing element, `None` when the misplaced element is a root.
+    pub parent: Option<NodeType>,
+    pub message: String,
+}
+
+impl fmt::Display for NestingWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+pub(crate) fn validate_nesting(roots: &[BevyNodeTree]) -> Vec<NestingWarning> {
+    let mut warnings = Vec::new();
+    for root in roots {
+        check_subtree(root, None, &mut warnings);
+    }
+    warnings
+}
+
+fn check_subtree(
+    tree: &BevyNodeTree,
+    parent: Option<&NodeType>,
+    warnings: &mut Vec<NestingWarning>,
+) {
+    let kind = &tree.node.node_kind.kind;
+    if let Some(expected) = expected_parent(kind)
+        && !parent.is_some_and(|parent| parent_fits(kind, parent))
+    {
+        let tag = kind.tag_name();
+        let message = match parent {
+            Some(parent) => format!(
+                "<{tag}> inside <{}> should be placed in {expected}",
+                parent.tag_name()
+            ),
+            None => format!("<{tag}> at the top level should be placed in {expected}"),
+        };
+        warnings.push(NestingWarning {
+            node_id: tree.node.id,
+            node_type: kind.clone(),
+            parent: parent.cloned(),
+            message,
+        });
+    }
+    for child in &tree.children {
+        check_subtree(child, Some(kind), warnings);
+    }
+}
+
+/// Where `kind` has to sit, worded for a warning, or `None` when it may go anywhere.
+fn expected_parent(kind: &NodeType) -> Option<&'static str> {
+    if kind.is_table_cell() {
+        return Some("a <tr>");
+    }
+    match kind {
+        NodeType::Li => Some("a <ul> or <ol>"),
+        NodeType::Tr => Some("a <table>, <thead>, <tbody> or <tfoot>"),
+        NodeType::Thead | NodeType::Tbody | NodeType::Tfoot => Some("a <table>"),
+        NodeType::Option => Some("a <select>"),
+        NodeType::Summary => Some("a <details>"),
+        _ => None,
+    }
+}
+
+fn parent_fits(kind: &NodeType, parent: &NodeType) -> bool {
+    if kind.is_table_cell() {
+        return *parent == NodeType::Tr;
+    }
+    match kind {
+        NodeType::Li => parent.is_list(),
+        NodeType::Tr => *parent == NodeType::Table || parent.is_table_section(),
+        NodeType::Thead | NodeType::Tbody | NodeType::Tfoot => *parent == NodeType::Table,
+        NodeType::Option => *parent == NodeType::Select,
+        NodeType::Summary => *parent == NodeType::Details,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevyml_parser::BevymlParser;
+
+    fn warnings(source: &str) -> Vec<NestingWarning> {
+        let roots: Vec<BevyNodeTree> = BevymlParser::new().parse(source).unwrap().into();
+        validate_nesting(&roots)
+    }
+
+    #[test]
+    fn cell_directly_under_body_is_flagged() {
+        let found = warnings("<body><td>stray</td></body>");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].node_type, NodeType::Td);
+        assert_eq!(found[0].parent, Some(NodeType::Body));
+    }
+
+    #[test]
+    fn well_formed_table_passes() {
+        let found = warnings(
+            "<table><thead><tr><th>a</th></tr></thead><tbody><tr><td>b</td></tr></tbody></table>",
+        );
+        assert!(found.is_empty(), "{found:?}");
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevyml_parser::attributes::Attributes;
+
+/// Finds spawned bevyml elements by their `id` and `class` attributes.
+#[derive(SystemParam)]
+pub struct BevymlQuery<'w, 's> {
+    elements: Query<'w, 's, (Entity, &'static Attributes)>,
+}
+
+impl BevymlQuery<'_, '_> {
+    /// The element whose `id` attribute equals `id`, if one is spawned.
+    pub fn by_id(&self, id: &str) -> Option<Entity> {
+        self.elements
+            .iter()
+            .find(|(_, attributes)| attributes.id() == Some(id))
+            .map(|(entity, _)| entity)
+    }
+
+    /// Every spawned element whose class list contains `class`.
+    pub fn by_class<'a>(&'a self, class: &'a str) -> impl Iterator<Item = Entity> + 'a {
+        self.elements
+            .iter()
+            .filter(move |(_, attributes)| attributes.has_class(class))
+            .map(|(entity, _)| entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::spawn_in_test_app;
+    use bevy::ecs::system::SystemState;
+
+    #[test]
+    fn finds_spawned_elements_by_id_and_class() {
+        let (mut app, roots) =
+            spawn_in_test_app(r#"<div><p id="title" class="big">a</p><p class="big">b</p></div>"#);
+        let world = app.world_mut();
+        let mut state = SystemState::<BevymlQuery>::new(world);
+        let query = state.get(world);
+        let title = query.by_id("title").unwrap();
+        assert_eq!(world.get::<ChildOf>(title).unwrap().parent(), roots[0]);
+        assert!(query.by_id("missing").is_none());
+        assert_eq!(query.by_class("big").count(), 2);
+    }
+}
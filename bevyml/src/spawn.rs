@@ -0,0 +1,290 @@
+use bevy::ui::{Checked, InteractionDisabled};
+use bevy::{ecs::relationship::RelatedSpawnerCommands, input_focus::InputFocus, prelude::*};
+use bevyml_components::NodeTypeMarker;
+use bevyml_parser::attributes::{DataSet, EventBindings};
+use bevyml_parser::inode::{
+    Autofocus, BevyNodeTree, HiddenUntilFound, Inert, NoTranslate, TextSection,
+};
+
+use crate::text::UnsetText;
+
+/// Spawns `tree` as a new UI hierarchy and returns its root entity.
+pub fn spawn_bevy_tree(commands: &mut Commands, tree: &BevyNodeTree) -> Entity {
+    let mut entity = commands.spawn(tree.node.clone());
+    insert_tree_components(&mut entity, tree);
+    entity.with_children(|parent| spawn_children(parent, tree));
+    entity.id()
+}
+
+/// Spawns `tree` below the entity `parent` is spawning children for.
+pub fn spawn_bevy_tree_child(
+    parent: &mut RelatedSpawnerCommands<'_, ChildOf>,
+    tree: &BevyNodeTree,
+) -> Entity {
+    let mut entity = parent.spawn(tree.node.clone());
+    insert_tree_components(&mut entity, tree);
+    entity.with_children(|parent| spawn_children(parent, tree));
+    entity.id()
+}
+
+fn spawn_children(parent: &mut RelatedSpawnerCommands<'_, ChildOf>, tree: &BevyNodeTree) {
+    if !tree.text_sections.is_empty() {
+        for section in &tree.text_sections {
+            spawn_text_section(parent, section);
+        }
+        return;
+    }
+    for child in &tree.children {
+        spawn_bevy_tree_child(parent, child);
+    }
+}
+
+fn spawn_text_section(parent: &mut RelatedSpawnerCommands<'_, ChildOf>, section: &TextSection) {
+    let mut span = parent.spawn((
+        TextSpan::new(section.text.clone()),
+        text_font(section.font_size, section.bold),
+        section.node_id,
+    ));
+    if let Some(color) = section.color {
+        span.insert(TextColor(color));
+    }
+    span.insert(UnsetText {
+        size: section.font_size.is_none(),
+        color: section.color.is_none(),
+    });
+}
+
+/// Bevy's default font, sized and weighted as the markup asks; `None` keeps the default size so
+/// [`BevymlTextDefaults`](crate::BevymlTextDefaults) can still fill it in.
+fn text_font(font_size: Option<f32>, bold: bool) -> TextFont {
+    let mut font = TextFont::default();
+    if let Some(font_size) = font_size {
+        font.font_size = font_size;
+    }
+    if bold {
+        font.weight = FontWeight::BOLD;
+    }
+    font
+}
+
+fn insert_tree_components(entity: &mut EntityCommands<'_>, tree: &BevyNodeTree) {
+    tree.node.node_kind.kind.insert_marker(entity);
+    if let Some(text) = tree.text.clone() {
+        entity.insert(text);
+    } else if !tree.text_sections.is_empty() {
+        entity.insert(Text::default());
+    }
+    if tree.font_size.is_some() || tree.bold {
+        entity.insert(text_font(tree.font_size, tree.bold));
+    }
+    if let Some(text_color) = tree.text_color {
+        entity.insert(TextColor(text_color));
+    }
+    if tree.node.node_kind.kind.can_contain_text() {
+        entity.insert_if_new((TextFont::default(), TextColor::default()));
+    }
+    if tree.font_size.is_some() || tree.bold || tree.node.node_kind.kind.can_contain_text() {
+        entity.insert(UnsetText {
+            size: tree.font_size.is_none(),
+            color: tree.text_color.is_none(),
+        });
+    }
+    if let Some(data_set) = DataSet::from_attributes(&tree.node.attributes) {
+        entity.insert(data_set);
+    }
+    if let Some(event_bindings) = EventBindings::from_attributes(&tree.node.attributes) {
+        entity.insert(event_bindings);
+    }
+    if let Some(max_lines) = tree.max_lines {
+        entity.insert(max_lines);
+    }
+    if let Some(toggle) = tree.toggle {
+        entity.insert(toggle);
+        if toggle.checked {
+            entity.insert(Checked);
+        }
+    }
+    if tree.disabled {
+        entity.insert(InteractionDisabled);
+    }
+    if let Some(box_shadow) = tree.box_shadow.clone() {
+        entity.insert(box_shadow);
+    }
+    if let Some(font_family) = tree.font_family.clone() {
+        entity.insert(font_family);
+    }
+    if let Some(transition) = tree.transition.clone() {
+        entity.insert(transition);
+    }
+    if let Some(hover_style) = tree.hover_style {
+        entity.insert(hover_style);
+    }
+    if let Some(checked_style) = tree.checked_style {
+        entity.insert(checked_style);
+    }
+    if let Some(disabled_style) = tree.disabled_style {
+        entity.insert(disabled_style);
+    }
+    if let Some(color_scheme_style) = tree.color_scheme_style {
+        entity.insert(color_scheme_style);
+    }
+    if let Some(cell_span) = tree.cell_span {
+        entity.insert(cell_span);
+    }
+    if let Some(progress) = tree.progress {
+        entity.insert(progress);
+    }
+    if let Some(disclosure) = tree.disclosure {
+        entity.insert(disclosure);
+    }
+    if let Some(disclosure_body) = tree.disclosure_body {
+        entity.insert(disclosure_body);
+    }
+    if let Some(image_source) = tree.image_source.clone() {
+        entity.insert(image_source);
+    }
+    if let Some(global_z_index) = tree.global_z_index {
+        entity.insert(global_z_index);
+    }
+    if let Some(cursor) = tree.cursor {
+        entity.insert(cursor);
+    }
+    if tree.hidden_until_found {
+        entity.insert((HiddenUntilFound, Visibility::Hidden));
+    }
+    if tree.no_translate {
+        entity.insert(NoTranslate);
+    }
+    if tree.autofocus {
+        entity.insert(Autofocus);
+    }
+    if tree.inert {
+        entity.insert(Inert);
+    } else if tree.node.node_kind.kind.is_interactive() {
+        entity.insert(Interaction::default());
+    }
+}
+
+/// Hands input focus to a freshly spawned `autofocus` element. Markup should only carry one; when
+/// several arrive together the first spawned wins.
+pub(crate) fn focus_autofocus_elements(
+    mut input_focus: ResMut<InputFocus>,
+    added: Query<Entity, Added<Autofocus>>,
+) {
+    let mut candidates = added.iter();
+    let Some(first) = candidates.next() else {
+        return;
+    };
+    let extra = candidates.count();
+    if extra > 0 {
+        warn!(
+            "{} autofocus elements spawned at once, focusing {first}",
+            extra + 1
+        );
+    }
+    input_focus.set(first);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::spawn_in_test_app;
+    use bevyml_parser::attributes::CursorStyle;
+    use bevyml_parser::inode::{NodeKind, NodeType, ToggleControl};
+
+    #[test]
+    fn controls_inside_inert_containers_get_no_interaction() {
+        let (mut app, _) = spawn_in_test_app(
+            r#"<div><div inert><button id="inert">a</button></div><button id="live">b</button></div>"#,
+        );
+        let mut buttons = app
+            .world_mut()
+            .query::<(&NodeKind, Has<Inert>, Has<Interaction>)>();
+        let buttons: Vec<_> = buttons
+            .iter(app.world())
+            .filter(|(kind, ..)| kind.kind == NodeType::Button)
+            .map(|(_, inert, interaction)| (inert, interaction))
+            .collect();
+        assert_eq!(buttons.len(), 2);
+        assert!(buttons.contains(&(true, false)));
+        assert!(buttons.contains(&(false, true)));
+    }
+
+    #[test]
+    fn cursor_style_is_attached_on_spawn() {
+        let (app, roots) = spawn_in_test_app(r#"<button style="cursor: pointer">Go</button>"#);
+        assert_eq!(
+            app.world().get::<CursorStyle>(roots[0]),
+            Some(&CursorStyle::Pointer)
+        );
+    }
+
+    #[test]
+    fn autofocus_input_receives_focus_after_spawn() {
+        let (mut app, _) = spawn_in_test_app("<div><p>Name</p><input autofocus /></div>");
+        app.init_resource::<InputFocus>()
+            .add_systems(PostUpdate, focus_autofocus_elements);
+        app.update();
+
+        let mut inputs = app.world_mut().query::<(Entity, &NodeKind)>();
+        let input = inputs
+            .iter(app.world())
+            .find(|(_, kind)| kind.kind == NodeType::Input)
+            .map(|(entity, _)| entity);
+        assert!(input.is_some());
+        assert_eq!(app.world().resource::<InputFocus>().get(), input);
+    }
+
+    #[test]
+    fn data_attributes_spawn_as_a_data_set() {
+        let (app, roots) = spawn_in_test_app(r#"<div data-x="1" data-y></div>"#);
+        let data = app.world().get::<DataSet>(roots[0]).unwrap();
+        assert_eq!(data.get("x"), Some("1"));
+        assert!(data.contains_key("y"));
+        assert_eq!(data.get("y"), None);
+    }
+
+    #[test]
+    fn checked_checkbox_spawns_a_checked_toggle() {
+        let (app, roots) = spawn_in_test_app(r#"<input type="checkbox" checked />"#);
+        let toggle = app.world().get::<ToggleControl>(roots[0]).unwrap();
+        assert!(toggle.checked);
+        assert!(!toggle.radio);
+    }
+
+    #[test]
+    fn mixed_inline_children_become_styled_spans() {
+        let (app, roots) = spawn_in_test_app("<p>a <strong>b</strong> c</p>");
+        let world = app.world();
+        assert!(world.get::<Text>(roots[0]).is_some());
+        let spans: Vec<_> = world
+            .get::<Children>(roots[0])
+            .unwrap()
+            .iter()
+            .map(|span| {
+                let text = world.get::<TextSpan>(span).unwrap().0.trim().to_string();
+                let bold = world.get::<TextFont>(span).unwrap().weight == FontWeight::BOLD;
+                (text, bold)
+            })
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("a".to_string(), false),
+                ("b".to_string(), true),
+                ("c".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn text_transform_applies_to_the_spawned_text() {
+        let (mut app, _) = spawn_in_test_app(
+            r#"<div><p style="text-transform: uppercase">hi</p><p style="text-transform: capitalize">hi</p></div>"#,
+        );
+        let mut texts = app.world_mut().query::<&Text>();
+        let mut rendered: Vec<_> = texts.iter(app.world()).map(|text| text.0.clone()).collect();
+        rendered.sort();
+        assert_eq!(rendered, ["HI", "Hi"]);
+    }
+}
@@ -0,0 +1,262 @@
+use bevy::prelude::*;
+use bevy::ui::{Checked, InteractionDisabled};
+use bevyml_parser::inode::{
+    CheckedStyle, ColorSchemeStyle, DisabledStyle, HoverStyle, NodeKind, NodeType, StateColors,
+    ToggleControl,
+};
+use bevyml_parser::stylesheet::ColorScheme;
+
+/// Colors an element showed before its state styles were first applied, restored once it leaves
+/// every styled state.
+#[derive(Copy, Clone, Debug, Component)]
+pub(crate) struct BaseColors {
+    background_color: Color,
+    text_color: Option<Color>,
+}
+
+type StyledElement<'a> = (
+    Entity,
+    Option<&'a HoverStyle>,
+    Option<&'a CheckedStyle>,
+    Option<&'a DisabledStyle>,
+    Option<&'a ColorSchemeStyle>,
+    Option<&'a Interaction>,
+    Has<Checked>,
+    Has<InteractionDisabled>,
+    Option<&'a BaseColors>,
+    Option<&'a Children>,
+    &'a mut BackgroundColor,
+    Option<&'a mut TextColor>,
+);
+
+type StyledFilter = Or<(
+    With<HoverStyle>,
+    With<CheckedStyle>,
+    With<DisabledStyle>,
+    With<ColorSchemeStyle>,
+)>;
+
+type UnstyledFilter = (
+    Without<HoverStyle>,
+    Without<CheckedStyle>,
+    Without<DisabledStyle>,
+    Without<ColorSchemeStyle>,
+);
+
+/// Keeps Bevy's `Checked` component in step with [`ToggleControl::checked`], so `:checked` styles
+/// follow the app flipping it.
+pub(crate) fn sync_checked(
+    mut commands: Commands,
+    toggles: Query<(Entity, &ToggleControl, Has<Checked>), Changed<ToggleControl>>,
+) {
+    for (entity, toggle, checked) in &toggles {
+        if toggle.checked && !checked {
+            commands.entity(entity).insert(Checked);
+        } else if !toggle.checked && checked {
+            commands.entity(entity).remove::<Checked>();
+        }
+    }
+}
+
+/// Swaps the background and text colors of elements with state styles while they are in those
+/// states, and puts the original colors back afterwards. Disabled beats checked, which beats
+/// hovered or pressed, which beats the colors for the current [`ColorScheme`], color by color.
+pub(crate) fn apply_state_styles(
+    mut commands: Commands,
+    color_scheme: Res<ColorScheme>,
+    mut styled: Query<StyledElement, StyledFilter>,
+    mut text_nodes: Query<(&NodeKind, &mut TextColor), UnstyledFilter>,
+) {
+    for (
+        entity,
+        hover,
+        checked_style,
+        disabled_style,
+        scheme_style,
+        interaction,
+        checked,
+        disabled,
+        base,
+        children,
+        mut background,
+        text_color,
+    ) in &mut styled
+    {
+        let base = match base {
+            Some(base) => *base,
+            None => {
+                let base = BaseColors {
+                    background_color: background.0,
+                    text_color: text_color.as_ref().map(|color| color.0),
+                };
+                commands.entity(entity).insert(base);
+                base
+            }
+        };
+        let hovered = matches!(
+            interaction,
+            Some(Interaction::Hovered | Interaction::Pressed)
+        );
+        let colors = [
+            disabled_style.filter(|_| disabled).map(|style| style.0),
+            checked_style.filter(|_| checked).map(|style| style.0),
+            hover.filter(|_| hovered).map(|style| style.0),
+            scheme_style.map(|style| match *color_scheme {
+                ColorScheme::Light => style.light,
+                ColorScheme::Dark => style.dark,
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .fold(StateColors::default(), |colors, layer| StateColors {
+            background_color: colors.background_color.or(layer.background_color),
+            text_color: colors.text_color.or(layer.text_color),
+        });
+        background.set_if_neq(BackgroundColor(
+            colors.background_color.unwrap_or(base.background_color),
+        ));
+        let Some(base_text_color) = base.text_color else {
+            continue;
+        };
+        let color = TextColor(colors.text_color.unwrap_or(base_text_color));
+        if let Some(mut text_color) = text_color {
+            text_color.set_if_neq(color);
+        }
+        let Some(children) = children else {
+            continue;
+        };
+        let mut iter = text_nodes.iter_many_mut(children);
+        while let Some((kind, mut text_color)) = iter.fetch_next() {
+            if kind.kind == NodeType::Text {
+                text_color.set_if_neq(color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::spawn_in_test_app;
+
+    #[test]
+    fn hovering_a_styled_button_applies_its_hover_background() {
+        let (mut app, roots) = spawn_in_test_app(
+            "<div><style>button { background-color: red } button:hover { background-color: blue; color: yellow }</style><button>Go</button></div>",
+        );
+        app.init_resource::<ColorScheme>()
+            .add_systems(PostUpdate, apply_state_styles);
+        let button = app
+            .world()
+            .get::<Children>(roots[0])
+            .unwrap()
+            .iter()
+            .find(|child| app.world().get::<HoverStyle>(*child).is_some())
+            .unwrap();
+        let label = app.world().get::<Children>(button).unwrap()[0];
+        let colors = |app: &App| {
+            (
+                app.world().get::<BackgroundColor>(button).unwrap().0,
+                app.world().get::<TextColor>(label).unwrap().0,
+            )
+        };
+
+        app.update();
+        let unhovered = colors(&app);
+        assert_eq!(unhovered.0, Color::srgb(1.0, 0.0, 0.0));
+
+        *app.world_mut().get_mut::<Interaction>(button).unwrap() = Interaction::Hovered;
+        app.update();
+        assert_eq!(
+            colors(&app),
+            (Color::srgb(0.0, 0.0, 1.0), Color::srgb(1.0, 1.0, 0.0))
+        );
+
+        *app.world_mut().get_mut::<Interaction>(button).unwrap() = Interaction::None;
+        app.update();
+        assert_eq!(colors(&app), unhovered);
+    }
+
+    #[test]
+    fn checked_rules_apply_only_to_checked_inputs() {
+        let (mut app, roots) = spawn_in_test_app(
+            r#"<div><style>input:checked { background-color: green }</style><input type="checkbox" checked /><input type="checkbox" /></div>"#,
+        );
+        app.init_resource::<ColorScheme>()
+            .add_systems(PostUpdate, (sync_checked, apply_state_styles).chain());
+        let inputs: Vec<_> = app
+            .world()
+            .get::<Children>(roots[0])
+            .unwrap()
+            .iter()
+            .filter(|child| app.world().get::<ToggleControl>(*child).is_some())
+            .collect();
+        let backgrounds = |app: &App| -> Vec<_> {
+            inputs
+                .iter()
+                .map(|input| app.world().get::<BackgroundColor>(*input).unwrap().0)
+                .collect()
+        };
+        let green = Color::from(bevy::color::palettes::basic::GREEN);
+
+        app.update();
+        assert_eq!(backgrounds(&app), [green, Color::NONE]);
+
+        for input in &inputs {
+            let mut toggle = app.world_mut().get_mut::<ToggleControl>(*input).unwrap();
+            toggle.checked = !toggle.checked;
+        }
+        app.update();
+        assert_eq!(backgrounds(&app), [Color::NONE, green]);
+    }
+
+    #[test]
+    fn disabled_colors_win_over_hover() {
+        let (mut app, roots) = spawn_in_test_app(
+            "<div><style>button:hover { background-color: blue } button:disabled { background-color: gray }</style><button disabled>Go</button></div>",
+        );
+        app.init_resource::<ColorScheme>()
+            .add_systems(PostUpdate, apply_state_styles);
+        let button = app
+            .world()
+            .get::<Children>(roots[0])
+            .unwrap()
+            .iter()
+            .find(|child| app.world().get::<InteractionDisabled>(*child).is_some())
+            .unwrap();
+        *app.world_mut().get_mut::<Interaction>(button).unwrap() = Interaction::Hovered;
+        app.update();
+        assert_eq!(
+            app.world().get::<BackgroundColor>(button).unwrap().0,
+            Color::from(bevy::color::palettes::basic::GRAY)
+        );
+    }
+
+    #[test]
+    fn dark_rules_apply_only_in_the_dark_color_scheme() {
+        let (mut app, roots) = spawn_in_test_app(
+            "<div><style>p { background-color: white } @media (prefers-color-scheme: dark) { p { background-color: black } }</style><p>x</p></div>",
+        );
+        app.init_resource::<ColorScheme>()
+            .add_systems(PostUpdate, apply_state_styles);
+        let p = app
+            .world()
+            .get::<Children>(roots[0])
+            .unwrap()
+            .iter()
+            .find(|child| app.world().get::<ColorSchemeStyle>(*child).is_some())
+            .unwrap();
+        let background = |app: &App| app.world().get::<BackgroundColor>(p).unwrap().0;
+
+        app.update();
+        assert_eq!(background(&app), Color::srgb(1.0, 1.0, 1.0));
+
+        *app.world_mut().resource_mut::<ColorScheme>() = ColorScheme::Dark;
+        app.update();
+        assert_eq!(background(&app), Color::srgb(0.0, 0.0, 0.0));
+
+        *app.world_mut().resource_mut::<ColorScheme>() = ColorScheme::Light;
+        app.update();
+        assert_eq!(background(&app), Color::srgb(1.0, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,255 @@
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use bevy::prelude::*;
+use bevyml_parser::attributes::Attribute;
+use bevyml_parser::inode::{BevyNodeTree, NodeType};
+use std::str;
+
+use crate::BevymlAssetLoaderError;
+
+/// A CSS file linked from markup with `<link rel="stylesheet">` or pulled in by `@import`. Unlike
+/// those of `<style>` elements, its rules are not applied to documents yet; loading it as a
+/// dependency lets apps wait for every sheet a document uses and hear about edits through
+/// `AssetEvent<BevymlStylesheet>`.
+#[derive(Asset, TypePath, Debug)]
+pub struct BevymlStylesheet {
+    pub source: String,
+    /// Sheets named by this sheet's leading `@import` rules, resolved next to it.
+    #[dependency]
+    pub imports: Vec<Handle<BevymlStylesheet>>,
+}
+
+#[derive(Default, TypePath)]
+pub struct BevymlStylesheetLoader;
+
+impl AssetLoader for BevymlStylesheetLoader {
+    type Asset = BevymlStylesheet;
+    type Settings = ();
+    type Error = BevymlAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let source = str::from_utf8(&bytes)?.to_string();
+        let imports = import_paths(&source)
+            .into_iter()
+            .filter_map(|path| load_relative(load_context, path))
+            .collect();
+        Ok(BevymlStylesheet { source, imports })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["css"]
+    }
+}
+
+/// Loads every sheet `roots` link to, followed by those `@import`ed from their `<style>` elements,
+/// as dependencies of the document being loaded.
+pub(crate) fn load_document_stylesheets(
+    roots: &[BevyNodeTree],
+    load_context: &mut LoadContext<'_>,
+) -> Vec<Handle<BevymlStylesheet>> {
+    let mut links = Vec::new();
+    let mut imports = Vec::new();
+    let mut stack: Vec<&BevyNodeTree> = roots.iter().rev().collect();
+    while let Some(tree) = stack.pop() {
+        match tree.node.node_kind.kind {
+            NodeType::Link => links.extend(stylesheet_href(tree)),
+            NodeType::Style => {
+                for child in &tree.children {
+                    if let Some(text) = &child.text {
+                        imports.extend(import_paths(&text.0).into_iter().map(str::to_string));
+                    }
+                }
+            }
+            _ => {}
+        }
+        stack.extend(tree.children.iter().rev());
+    }
+    links
+        .into_iter()
+        .chain(imports)
+        .filter_map(|path| load_relative(load_context, &path))
+        .collect()
+}
+
+/// The `href` of a `<link>` whose `rel` lists `stylesheet`.
+fn stylesheet_href(link: &BevyNodeTree) -> Option<String> {
+    let attributes = &link.node.attributes.items;
+    let is_stylesheet = attributes.iter().any(|attribute| match attribute {
+        Attribute::Rel(rel) => rel
+            .split_ascii_whitespace()
+            .any(|token| token.eq_ignore_ascii_case("stylesheet")),
+        _ => false,
+    });
+    if !is_stylesheet {
+        return None;
+    }
+    attributes.iter().find_map(|attribute| match attribute {
+        Attribute::Href(href) if !href.trim().is_empty() => Some(href.trim().to_string()),
+        _ => None,
+    })
+}
+
+/// Paths named by the `@import` rules at the top of `source`. CSS ignores imports after the first
+/// other rule, so scanning stops there.
+fn import_paths(source: &str) -> Vec<&str> {
+    let mut paths = Vec::new();
+    for statement in source.split(';') {
+        let statement = statement.trim_start();
+        if statement.contains('{') {
+            break;
+        }
+        let Some(rest) = statement
+            .get(..7)
+            .filter(|keyword| keyword.eq_ignore_ascii_case("@import"))
+            .map(|_| statement[7..].trim())
+        else {
+            continue;
+        };
+        if let Some(path) = import_target(rest) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// The path of an `@import` written as `"path"`, `'path'`, `url(path)` or `url("path")`; media
+/// queries and layers after it are ignored.
+fn import_target(rest: &str) -> Option<&str> {
+    let target = match rest.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("url(") => {
+            let inner = &rest[4..];
+            inner[..inner.find(')')?].trim()
+        }
+        _ => rest.split_ascii_whitespace().next()?,
+    };
+    let unquoted = target
+        .strip_prefix('"')
+        .and_then(|target| target.strip_suffix('"'))
+        .or_else(|| {
+            target
+                .strip_prefix('\'')
+                .and_then(|target| target.strip_suffix('\''))
+        })
+        .unwrap_or(target);
+    Some(unquoted).filter(|path| !path.is_empty())
+}
+
+/// Loads `path` resolved against the asset being loaded, as a browser resolves a relative URL.
+fn load_relative(
+    load_context: &mut LoadContext<'_>,
+    path: &str,
+) -> Option<Handle<BevymlStylesheet>> {
+    match load_context.path().resolve_embed(path) {
+        Ok(resolved) => Some(load_context.load(resolved)),
+        Err(err) => {
+            warn!("skipping stylesheet {path:?}: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BevymlAsset, BevymlAssetLoader};
+    use bevy::asset::io::memory::{Dir, MemoryAssetReader};
+    use bevy::asset::io::{AssetSourceBuilder, AssetSourceId};
+    use bevyml_parser::BevymlParser;
+    use std::path::Path;
+
+    fn link(source: &str) -> Option<String> {
+        let roots: Vec<BevyNodeTree> = BevymlParser::new().parse(source).unwrap().into();
+        stylesheet_href(&roots[0])
+    }
+
+    #[test]
+    fn import_target_accepts_quotes_and_url() {
+        assert_eq!(import_target(r#""a.css""#), Some("a.css"));
+        assert_eq!(import_target("'a.css'"), Some("a.css"));
+        assert_eq!(import_target("url(a.css)"), Some("a.css"));
+        assert_eq!(import_target(r#"URL( "a.css" )"#), Some("a.css"));
+        assert_eq!(
+            import_target(r#""a.css" screen and (min-width: 600px)"#),
+            Some("a.css")
+        );
+        assert_eq!(import_target(r#""""#), None);
+    }
+
+    #[test]
+    fn import_paths_stop_at_the_first_rule() {
+        let source = r#"
+            @import "base.css";
+            @import url(theme.css) screen;
+            @IMPORT 'print.css' print;
+            p { color: red; }
+            @import "late.css";
+        "#;
+        assert_eq!(import_paths(source), ["base.css", "theme.css", "print.css"]);
+    }
+
+    #[test]
+    fn only_stylesheet_links_yield_an_href() {
+        assert_eq!(
+            link(r#"<link rel="alternate stylesheet" href=" dark.css " />"#),
+            Some("dark.css".to_string())
+        );
+        assert_eq!(link(r#"<link rel="icon" href="icon.png" />"#), None);
+        assert_eq!(link(r#"<link rel="stylesheet" href="" />"#), None);
+    }
+
+    #[test]
+    fn linked_stylesheets_load_as_document_dependencies() {
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("ui/page.bevyml"),
+            r#"<div><link rel="stylesheet" href="theme.css" /><p>hi</p></div>"#,
+        );
+        dir.insert_asset_text(
+            Path::new("ui/theme.css"),
+            r#"@import "base.css"; p { color: red; }"#,
+        );
+        dir.insert_asset_text(Path::new("ui/base.css"), "div { width: 10px; }");
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<BevymlAsset>()
+        .init_asset::<BevymlStylesheet>()
+        .init_asset_loader::<BevymlAssetLoader>()
+        .init_asset_loader::<BevymlStylesheetLoader>();
+        let page: Handle<BevymlAsset> =
+            app.world().resource::<AssetServer>().load("ui/page.bevyml");
+
+        for _ in 0..10_000 {
+            app.update();
+            let server = app.world().resource::<AssetServer>();
+            if server.is_loaded_with_dependencies(&page) {
+                break;
+            }
+            assert!(!server.load_state(&page).is_failed(), "page failed to load");
+        }
+
+        let world = app.world();
+        let stylesheets = world.resource::<Assets<BevymlStylesheet>>();
+        let page = world.resource::<Assets<BevymlAsset>>().get(&page).unwrap();
+        assert_eq!(page.stylesheets.len(), 1);
+        let theme = stylesheets.get(&page.stylesheets[0]).unwrap();
+        assert_eq!(theme.source, r#"@import "base.css"; p { color: red; }"#);
+        assert_eq!(
+            world
+                .resource::<AssetServer>()
+                .get_path(&page.stylesheets[0]),
+            Some("ui/theme.css".into())
+        );
+        let base = stylesheets.get(&theme.imports[0]).unwrap();
+        assert_eq!(base.source, "div { width: 10px; }");
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use bevyml_parser::BevymlParser;
+use bevyml_parser::inode::BevyNodeTree;
+
+use crate::spawn_bevy_tree;
+
+/// Parses `source` with default settings and spawns every root into a fresh headless [`App`],
+/// returning the app and the root entities in document order. No plugins are added, so systems
+/// like [`BevymlAssetPlugin`](crate::BevymlAssetPlugin)'s have not run on the result.
+///
+/// Panics when `source` fails to parse, as befits a test helper.
+pub fn spawn_in_test_app(source: &str) -> (App, Vec<Entity>) {
+    let tree = BevymlParser::new()
+        .parse(source)
+        .expect("test markup should parse");
+    let roots: Vec<BevyNodeTree> = tree.into();
+    let mut app = App::new();
+    let world = app.world_mut();
+    let entities = {
+        let mut commands = world.commands();
+        roots
+            .iter()
+            .map(|root| spawn_bevy_tree(&mut commands, root))
+            .collect()
+    };
+    world.flush();
+    (app, entities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NodeKind, NodeType};
+
+    #[test]
+    fn spawns_each_root_with_its_node_kind() {
+        let (app, roots) = spawn_in_test_app("<div><p>hello</p></div><span>world</span>");
+        let kinds: Vec<_> = roots
+            .iter()
+            .map(|root| app.world().get::<NodeKind>(*root).unwrap().kind.clone())
+            .collect();
+        assert_eq!(kinds, [NodeType::Div, NodeType::Span]);
+    }
+}
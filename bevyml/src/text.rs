@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+
+/// Project-wide font settings for text spawned from bevyml trees, applied to text nodes and to
+/// every element that can contain text. Only the size and color the markup left unset are filled
+/// in, so per-element styles keep priority even when they match Bevy's defaults; the font replaces
+/// Bevy's default one.
+#[derive(Resource, Clone, Debug)]
+pub struct BevymlTextDefaults {
+    pub font: Handle<Font>,
+    pub size: f32,
+    pub color: Color,
+}
+
+impl Default for BevymlTextDefaults {
+    fn default() -> Self {
+        Self {
+            font: Handle::default(),
+            size: TextFont::default().font_size,
+            color: TextColor::default().0,
+        }
+    }
+}
+
+/// Which text properties neither a spawned element nor its ancestors declared, decided at spawn
+/// time so [`apply_text_defaults`] knows what to fill in.
+#[derive(Component, Clone, Copy, Debug)]
+pub(crate) struct UnsetText {
+    pub(crate) size: bool,
+    pub(crate) color: bool,
+}
+
+pub(crate) fn apply_text_defaults(
+    defaults: Res<BevymlTextDefaults>,
+    mut texts: Query<(&UnsetText, &mut TextFont, &mut TextColor), Added<UnsetText>>,
+) {
+    let bevy_font = TextFont::default();
+    for (unset, mut font, mut color) in &mut texts {
+        if font.font == bevy_font.font {
+            font.font = defaults.font.clone();
+        }
+        if unset.size {
+            font.font_size = defaults.size;
+        }
+        if unset.color {
+            color.0 = defaults.color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::spawn_in_test_app;
+
+    #[test]
+    fn unstyled_text_takes_the_default_size() {
+        let (mut app, _) =
+            spawn_in_test_app(r#"<div><p>plain</p><p style="font-size: 12px">sized</p></div>"#);
+        app.insert_resource(BevymlTextDefaults {
+            size: 30.0,
+            ..Default::default()
+        })
+        .add_systems(Update, apply_text_defaults);
+        app.update();
+
+        let mut texts = app.world_mut().query::<(&Text, &TextFont)>();
+        let mut sizes: Vec<_> = texts
+            .iter(app.world())
+            .map(|(text, font)| (text.0.clone(), font.font_size))
+            .collect();
+        sizes.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            sizes,
+            [("plain".to_string(), 30.0), ("sized".to_string(), 12.0)]
+        );
+    }
+
+    #[test]
+    fn explicit_styles_matching_bevy_defaults_are_kept() {
+        let (mut app, _) = spawn_in_test_app(
+            r#"<div><p style="font-size: 20px; color: white">explicit</p><p>plain <b>bold</b></p></div>"#,
+        );
+        app.insert_resource(BevymlTextDefaults {
+            size: 30.0,
+            color: Color::srgb(1.0, 0.0, 0.0),
+            ..Default::default()
+        })
+        .add_systems(Update, apply_text_defaults);
+        app.update();
+
+        let mut texts = app
+            .world_mut()
+            .query::<(&TextSpan, &TextFont, &TextColor)>();
+        let mut spans: Vec<_> = texts
+            .iter(app.world())
+            .map(|(span, font, color)| (span.0.trim().to_string(), font.font_size, color.0))
+            .collect();
+        let mut texts = app.world_mut().query::<(&Text, &TextFont, &TextColor)>();
+        spans.extend(
+            texts
+                .iter(app.world())
+                .filter(|(text, ..)| !text.0.is_empty())
+                .map(|(text, font, color)| (text.0.clone(), font.font_size, color.0)),
+        );
+        spans.sort_by(|a, b| a.0.cmp(&b.0));
+        let red = Color::srgb(1.0, 0.0, 0.0);
+        assert_eq!(
+            spans,
+            [
+                ("bold".to_string(), 30.0, red),
+                ("explicit".to_string(), 20.0, Color::srgb(1.0, 1.0, 1.0)),
+                ("plain".to_string(), 30.0, red),
+            ]
+        );
+    }
+}